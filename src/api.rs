@@ -0,0 +1,104 @@
+use crate::{
+  bot::{Bot, Stats},
+  user::{User, Voter},
+  Client, Result,
+};
+
+/// An async trait covering the [Top.gg API](https://docs.top.gg) methods of [`Client`], so that code using it can be unit-tested against a mock implementation instead. (See [`MockApi`][crate::testing::MockApi] with the `testing` feature enabled)
+///
+/// IDs are taken as [`u64`]s so that this trait can be used as a trait object, e.g. `Arc<dyn TopggApi>`.
+///
+/// It's described as follows (without [`async_trait`]'s macro expansion):
+/// ```rust,no_run
+/// # use topgg::{bot::Bot, user::{User, Voter}, Result, Stats};
+/// #[async_trait::async_trait]
+/// pub trait TopggApi: Send + Sync {
+///   async fn get_user(&self, id: u64) -> Result<User>;
+///   async fn get_bot(&self, id: u64) -> Result<Bot>;
+///   async fn get_stats(&self) -> Result<Stats>;
+///   async fn post_stats(&self, new_stats: Stats) -> Result<()>;
+///   async fn get_voters(&self) -> Result<Vec<Voter>>;
+///   async fn has_voted(&self, user_id: u64) -> Result<bool>;
+///   async fn is_weekend(&self) -> Result<bool>;
+/// }
+/// ```
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust,no_run
+/// use topgg::TopggApi;
+///
+/// async fn vote_locked_command<A>(api: &A, user_id: u64) -> &'static str
+/// where
+///   A: TopggApi + ?Sized,
+/// {
+///   match api.has_voted(user_id).await {
+///     Ok(true) => "thanks for voting!",
+///     Ok(false) => "please vote first!",
+///     Err(_) => "something went wrong.",
+///   }
+/// }
+/// ```
+#[async_trait::async_trait]
+pub trait TopggApi: Send + Sync {
+  /// Fetches a user from a Discord ID. (See [`Client::get_user`])
+  async fn get_user(&self, id: u64) -> Result<User>;
+
+  /// Fetches a listed Discord bot from a Discord ID. (See [`Client::get_bot`])
+  async fn get_bot(&self, id: u64) -> Result<Bot>;
+
+  /// Fetches your Discord bot's statistics. (See [`Client::get_stats`])
+  async fn get_stats(&self) -> Result<Stats>;
+
+  /// Posts your Discord bot's statistics. (See [`Client::post_stats`])
+  async fn post_stats(&self, new_stats: Stats) -> Result<()>;
+
+  /// Fetches your Discord bot's last 1000 voters. (See [`Client::get_voters`])
+  async fn get_voters(&self) -> Result<Vec<Voter>>;
+
+  /// Checks if the specified user has voted your Discord bot. (See [`Client::has_voted`])
+  async fn has_voted(&self, user_id: u64) -> Result<bool>;
+
+  /// Checks if the weekend multiplier is active. (See [`Client::is_weekend`])
+  async fn is_weekend(&self) -> Result<bool>;
+}
+
+#[async_trait::async_trait]
+impl TopggApi for Client {
+  #[inline(always)]
+  async fn get_user(&self, id: u64) -> Result<User> {
+    Client::get_user(self, id).await
+  }
+
+  #[inline(always)]
+  async fn get_bot(&self, id: u64) -> Result<Bot> {
+    Client::get_bot(self, id).await
+  }
+
+  #[inline(always)]
+  async fn get_stats(&self) -> Result<Stats> {
+    Client::get_stats(self).await
+  }
+
+  #[inline(always)]
+  async fn post_stats(&self, new_stats: Stats) -> Result<()> {
+    Client::post_stats(self, new_stats).await
+  }
+
+  #[inline(always)]
+  async fn get_voters(&self) -> Result<Vec<Voter>> {
+    Client::get_voters(self).await
+  }
+
+  #[inline(always)]
+  async fn has_voted(&self, user_id: u64) -> Result<bool> {
+    Client::has_voted(self, user_id).await
+  }
+
+  #[inline(always)]
+  async fn is_weekend(&self) -> Result<bool> {
+    Client::is_weekend(self).await
+  }
+}
@@ -165,13 +165,12 @@ where
         loop {
           handler.stats().wait().await;
 
-          {
-            let stats = handler.stats().stats.read().await;
+          // cloned so that the lock isn't held while retrying or waiting for a ratelimit.
+          let stats = handler.stats().stats.read().await.clone();
 
-            if sender.send(client.post_stats(&stats).await).is_err() {
-              break;
-            }
-          };
+          if sender.send(client.post_stats(&stats).await).is_err() {
+            break;
+          }
 
           sleep(interval).await;
         }
@@ -187,6 +186,8 @@ where
   }
   
   /// Returns a future that resolves every time the [`Autoposter`] has attempted to post the bot's stats. If you want to use the receiver directly, call [`receiver`].
+  ///
+  /// An invalid or revoked [Top.gg API](https://docs.top.gg) token is reported here as an [`Unauthorized`][crate::Error::Unauthorized] error, the [`Autoposter`] keeps running afterwards.
   #[inline(always)]
   pub async fn recv(&mut self) -> Option<Result<()>> {
     self.receiver.as_mut().expect("receiver is already taken from the receiver() method. please call recv() directly from the receiver.").recv().await
@@ -1,11 +1,17 @@
-use crate::{snowflake, util};
+use crate::{snowflake, util, InnerClient, Result};
 use chrono::{DateTime, Utc};
+use core::{
+  future::{Future, IntoFuture},
+  pin::Pin,
+};
+use futures_util::stream::{self, Stream, TryStreamExt};
+use reqwest::{Method, Url};
 use serde::{Deserialize, Deserializer, Serialize};
 
 #[inline(always)]
 pub(crate) fn deserialize_support_server<'de, D>(
   deserializer: D,
-) -> Result<Option<String>, D::Error>
+) -> core::result::Result<Option<String>, D::Error>
 where
   D: Deserializer<'de>,
 {
@@ -159,6 +165,269 @@ util::debug_struct! {
   }
 }
 
+/// The fields that are always requested from [`Client::get_bots`][crate::Client::get_bots] regardless of [`GetBots::fields`], as they are needed to deserialize a [`Bot`].
+const REQUIRED_FIELDS: [&str; 10] = [
+  "id",
+  "username",
+  "discriminator",
+  "prefix",
+  "shortdesc",
+  "owners",
+  "date",
+  "certifiedBot",
+  "points",
+  "monthlyPoints",
+];
+
+/// A struct representing a single page of Discord bots listed on [Top.gg](https://top.gg). (See [`Client::get_bots`][crate::Client::get_bots])
+#[must_use]
+#[derive(Clone, Debug, Deserialize)]
+pub struct Bots {
+  /// The Discord bots in this page.
+  pub results: Vec<Bot>,
+
+  /// The maximum amount of Discord bots requested for this page.
+  pub limit: usize,
+
+  /// The amount of Discord bots skipped before this page.
+  pub offset: usize,
+
+  /// The amount of Discord bots in this page.
+  pub count: usize,
+
+  /// The total amount of Discord bots matching the query.
+  pub total: usize,
+}
+
+/// A query builder for searching and listing Discord bots listed on [Top.gg](https://top.gg). Created from [`Client::get_bots`][crate::Client::get_bots].
+///
+/// This struct can either be `.await`ed directly to fetch a single [`Bots`] page, or be turned into a [`Stream`] of [`Bot`]s with [`into_stream`][GetBots::into_stream].
+///
+/// # Examples
+///
+/// Fetching a single page:
+///
+/// ```rust,no_run
+/// use topgg::Client;
+///
+/// # async fn run(client: Client) -> topgg::Result<()> {
+/// let bots = client
+///   .get_bots()
+///   .search("tags", "moderation")
+///   .sort_by_monthly_votes()
+///   .limit(250)
+///   .await?;
+///
+/// for bot in bots.results {
+///   println!("{:?}", bot);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+///
+/// Walking through every page:
+///
+/// ```rust,no_run
+/// use futures_util::StreamExt;
+/// use topgg::Client;
+///
+/// # async fn run(client: Client) -> topgg::Result<()> {
+/// let mut bots = Box::pin(client.get_bots().search("tags", "moderation").into_stream());
+///
+/// while let Some(bot) = bots.next().await {
+///   println!("{:?}", bot?);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[must_use]
+#[derive(Clone)]
+pub struct GetBots<'a> {
+  client: &'a InnerClient,
+  search: Vec<String>,
+  sort: Option<&'static str>,
+  descending: bool,
+  limit: usize,
+  offset: usize,
+  fields: Vec<String>,
+}
+
+impl<'a> GetBots<'a> {
+  /// The maximum amount of Discord bots that can be requested in a single page.
+  pub const MAX_LIMIT: usize = 500;
+
+  #[inline(always)]
+  pub(crate) fn new(client: &'a InnerClient) -> Self {
+    Self {
+      client,
+      search: Vec::new(),
+      sort: None,
+      descending: false,
+      limit: 50,
+      offset: 0,
+      fields: Vec::new(),
+    }
+  }
+
+  /// Filters the results by a field's value, e.g. `search("username", "shiro")`. This can be called multiple times to filter by several fields.
+  pub fn search<F, V>(mut self, field: F, value: V) -> Self
+  where
+    F: AsRef<str>,
+    V: AsRef<str>,
+  {
+    self
+      .search
+      .push(format!("{}: {}", field.as_ref(), value.as_ref()));
+    self
+  }
+
+  /// Sorts the results by the amount of upvotes each Discord bot has this month.
+  #[inline(always)]
+  pub fn sort_by_monthly_votes(mut self) -> Self {
+    self.sort = Some("monthlyPoints");
+    self
+  }
+
+  /// Sorts the results by the amount of upvotes each Discord bot has.
+  #[inline(always)]
+  pub fn sort_by_votes(mut self) -> Self {
+    self.sort = Some("points");
+    self
+  }
+
+  /// Sorts the results by the date when each Discord bot was approved on [Top.gg](https://top.gg).
+  #[inline(always)]
+  pub fn sort_by_approval_date(mut self) -> Self {
+    self.sort = Some("date");
+    self
+  }
+
+  /// Reverses the sorting order of the results. This has no effect if no sorting method is specified.
+  #[inline(always)]
+  pub fn descending(mut self) -> Self {
+    self.descending = true;
+    self
+  }
+
+  /// Sets the maximum amount of Discord bots to be returned per page. Defaults to 50 and is capped at [`MAX_LIMIT`][GetBots::MAX_LIMIT].
+  #[inline(always)]
+  pub fn limit(mut self, limit: usize) -> Self {
+    self.limit = limit.clamp(1, Self::MAX_LIMIT);
+    self
+  }
+
+  /// Sets the amount of Discord bots to be skipped. Defaults to zero.
+  #[inline(always)]
+  pub fn offset(mut self, offset: usize) -> Self {
+    self.offset = offset;
+    self
+  }
+
+  /// Only requests the specified fields from [Top.gg](https://top.gg), e.g. `fields(["tags", "shards"])`.
+  ///
+  /// The fields required to deserialize a [`Bot`] are always requested as well, so the omitted optional fields will simply be empty.
+  pub fn fields<I, F>(mut self, fields: I) -> Self
+  where
+    I: IntoIterator<Item = F>,
+    F: Into<String>,
+  {
+    self.fields.extend(fields.into_iter().map(Into::into));
+    self
+  }
+
+  fn url(&self) -> Url {
+    let mut url = Url::parse(&util::api!(self.client, "/bots")).unwrap();
+
+    {
+      let mut pairs = url.query_pairs_mut();
+
+      pairs
+        .append_pair("limit", &self.limit.to_string())
+        .append_pair("offset", &self.offset.to_string());
+
+      if !self.search.is_empty() {
+        pairs.append_pair("search", &self.search.join(" "));
+      }
+
+      if let Some(sort) = self.sort {
+        if self.descending {
+          pairs.append_pair("sort", &format!("-{sort}"));
+        } else {
+          pairs.append_pair("sort", sort);
+        }
+      }
+
+      if !self.fields.is_empty() {
+        let mut fields = REQUIRED_FIELDS.map(String::from).to_vec();
+
+        for field in &self.fields {
+          if !fields.contains(field) {
+            fields.push(field.clone());
+          }
+        }
+
+        pairs.append_pair("fields", &fields.join(","));
+      }
+    }
+
+    url
+  }
+
+  /// Fetches a single page of Discord bots with the current query. This is akin to `.await`ing this struct directly.
+  ///
+  /// # Errors
+  ///
+  /// Errors if any of the following conditions are met:
+  /// - An internal error from the client itself preventing it from sending a HTTP request to [Top.gg](https://top.gg) ([`InternalClientError`][crate::Error::InternalClientError])
+  /// - An unexpected HTTP response from the [Top.gg](https://top.gg) servers ([`Http`][crate::Error::Http])
+  /// - The response from the [Top.gg](https://top.gg) servers cannot be deserialized ([`Deserialize`][crate::Error::Deserialize])
+  /// - The request timed out ([`Timeout`][crate::Error::Timeout])
+  /// - The client uses an invalid [Top.gg API](https://docs.top.gg) token ([`Unauthorized`][crate::Error::Unauthorized])
+  /// - The client's [Top.gg API](https://docs.top.gg) token is not allowed to access the requested resource ([`Forbidden`][crate::Error::Forbidden])
+  /// - The client is being ratelimited from sending more HTTP requests ([`Ratelimit`][crate::Error::Ratelimit])
+  #[inline(always)]
+  pub async fn send(&self) -> Result<Bots> {
+    self.client.send(Method::GET, self.url(), None).await
+  }
+
+  /// Turns this query into a [`Stream`] of [`Bot`]s, fetching every page starting from the current offset until there are no more results.
+  ///
+  /// The stream ends right after yielding an error.
+  pub fn into_stream(self) -> impl Stream<Item = Result<Bot>> + 'a {
+    stream::unfold(Some(self), |query| async move {
+      let mut query = query?;
+
+      match query.send().await {
+        Ok(page) => {
+          query.offset += page.results.len();
+
+          let next = if page.results.is_empty() || query.offset >= page.total {
+            None
+          } else {
+            Some(query)
+          };
+
+          Some((Ok(page.results), next))
+        }
+
+        Err(err) => Some((Err(err), None)),
+      }
+    })
+    .map_ok(|results| stream::iter(results.into_iter().map(Ok)))
+    .try_flatten()
+  }
+}
+
+impl<'a> IntoFuture for GetBots<'a> {
+  type Output = Result<Bots>;
+  type IntoFuture = Pin<Box<dyn Future<Output = Self::Output> + Send + 'a>>;
+
+  #[inline(always)]
+  fn into_future(self) -> Self::IntoFuture {
+    Box::pin(async move { self.send().await })
+  }
+}
+
 util::debug_struct! {
   /// A struct representing a Discord bot's statistics.
   ///
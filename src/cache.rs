@@ -0,0 +1,247 @@
+use crate::{bot::Bot, user::User, Result};
+use core::{fmt, future::Future, time::Duration};
+use std::{
+  collections::HashMap,
+  sync::{Arc, Mutex},
+  time::Instant,
+};
+use tokio::sync::Mutex as AsyncMutex;
+
+/// A struct representing the configuration of a [`Client`][crate::Client]'s in-memory response cache. (See [`ClientBuilder::cache`][crate::ClientBuilder::cache])
+///
+/// Concurrent lookups of the same ID are coalesced into a single HTTP request. Errors are never cached.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust,no_run
+/// use core::time::Duration;
+/// use topgg::{CachePolicy, Client};
+///
+/// # fn run() -> topgg::Result<()> {
+/// let client = Client::builder(String::from("my-topgg-token"))
+///   .cache(
+///     CachePolicy::new()
+///       .bot_ttl(Duration::from_secs(60))
+///       .max_entries(500),
+///   )
+///   .build()?;
+/// # Ok(())
+/// # }
+/// ```
+#[must_use]
+#[derive(Clone, Debug)]
+pub struct CachePolicy {
+  bot_ttl: Duration,
+  user_ttl: Duration,
+  max_entries: usize,
+}
+
+impl CachePolicy {
+  /// Creates a [`CachePolicy`] that caches [`Client::get_bot`][crate::Client::get_bot] and [`Client::get_user`][crate::Client::get_user] responses for 5 minutes, with at most 1000 entries per endpoint.
+  pub const fn new() -> Self {
+    Self {
+      bot_ttl: Duration::from_secs(300),
+      user_ttl: Duration::from_secs(300),
+      max_entries: 1000,
+    }
+  }
+
+  /// Sets how long a [`Client::get_bot`][crate::Client::get_bot] response stays cached.
+  #[inline(always)]
+  pub const fn bot_ttl(mut self, ttl: Duration) -> Self {
+    self.bot_ttl = ttl;
+    self
+  }
+
+  /// Sets how long a [`Client::get_user`][crate::Client::get_user] response stays cached.
+  #[inline(always)]
+  pub const fn user_ttl(mut self, ttl: Duration) -> Self {
+    self.user_ttl = ttl;
+    self
+  }
+
+  /// Sets the maximum amount of entries cached per endpoint. Once reached, expired entries are removed first, then the oldest ones.
+  #[inline(always)]
+  pub const fn max_entries(mut self, max_entries: usize) -> Self {
+    self.max_entries = max_entries;
+    self
+  }
+}
+
+impl Default for CachePolicy {
+  #[inline(always)]
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+type Slot<T> = Arc<AsyncMutex<Option<(Instant, T)>>>;
+
+pub(crate) struct TtlCache<T> {
+  ttl: Duration,
+  max_entries: usize,
+  entries: Mutex<HashMap<u64, Slot<T>>>,
+}
+
+impl<T> TtlCache<T>
+where
+  T: Clone,
+{
+  fn new(ttl: Duration, max_entries: usize) -> Self {
+    Self {
+      ttl,
+      max_entries,
+      entries: Mutex::new(HashMap::new()),
+    }
+  }
+
+  // removes expired entries, then the oldest ones until there's room for a new entry.
+  // entries that are currently being fetched are left alone.
+  fn evict(&self, entries: &mut HashMap<u64, Slot<T>>) {
+    let mut fetched = Vec::new();
+
+    entries.retain(|id, slot| match slot.try_lock() {
+      Ok(slot) => match &*slot {
+        Some((fetched_at, _)) if fetched_at.elapsed() < self.ttl => {
+          fetched.push((*fetched_at, *id));
+          true
+        }
+        _ => false,
+      },
+      _ => true,
+    });
+
+    if entries.len() >= self.max_entries {
+      fetched.sort_unstable();
+
+      for (_, id) in fetched
+        .into_iter()
+        .take(entries.len() + 1 - self.max_entries)
+      {
+        entries.remove(&id);
+      }
+    }
+  }
+
+  pub(crate) async fn get_or_fetch<F, Fut>(&self, id: u64, fetch: F) -> Result<T>
+  where
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = Result<T>>,
+  {
+    let slot = {
+      let mut entries = self.entries.lock().unwrap();
+
+      if !entries.contains_key(&id) && entries.len() >= self.max_entries {
+        self.evict(&mut entries);
+      }
+
+      Arc::clone(entries.entry(id).or_default())
+    };
+
+    // concurrent lookups of the same ID wait here until the first one is done fetching.
+    let mut slot = slot.lock().await;
+
+    if let Some((fetched_at, value)) = &*slot {
+      if fetched_at.elapsed() < self.ttl {
+        return Ok(value.clone());
+      }
+    }
+
+    let value = fetch().await?;
+
+    *slot = Some((Instant::now(), value.clone()));
+
+    Ok(value)
+  }
+
+  #[inline(always)]
+  pub(crate) fn invalidate(&self, id: u64) {
+    self.entries.lock().unwrap().remove(&id);
+  }
+
+  #[inline(always)]
+  pub(crate) fn clear(&self) {
+    self.entries.lock().unwrap().clear();
+  }
+}
+
+pub(crate) struct ResponseCache {
+  pub(crate) bots: TtlCache<Bot>,
+  pub(crate) users: TtlCache<User>,
+}
+
+impl ResponseCache {
+  pub(crate) fn new(policy: CachePolicy) -> Self {
+    Self {
+      bots: TtlCache::new(policy.bot_ttl, policy.max_entries),
+      users: TtlCache::new(policy.user_ttl, policy.max_entries),
+    }
+  }
+}
+
+impl fmt::Debug for ResponseCache {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("ResponseCache").finish_non_exhaustive()
+  }
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+  use super::CachePolicy;
+  use crate::testing::FakeTopgg;
+  use futures_util::future::join_all;
+  use serde_json::json;
+
+  #[tokio::test]
+  async fn coalesces_concurrent_lookups() {
+    let fake = FakeTopgg::start().await.unwrap();
+    let client = fake
+      .client_builder()
+      .cache(CachePolicy::new())
+      .build()
+      .unwrap();
+
+    fake.add_bot(json!({
+      "id": "264811613708746752",
+      "username": "Luca",
+      "discriminator": "1375",
+      "prefix": "- or @Luca",
+      "shortdesc": "Luca is a bot for managing and informing members of the server",
+      "owners": ["129908908096487424"],
+      "date": "2017-04-26T18:08:17.125Z",
+      "certifiedBot": false,
+      "points": 397,
+      "monthlyPoints": 19,
+    }));
+
+    let bots = join_all((0..10).map(|_| client.get_bot(264811613708746752))).await;
+
+    assert!(bots
+      .iter()
+      .all(|bot| bot.as_ref().unwrap().username == "Luca"));
+    assert_eq!(fake.requests(), 1);
+
+    // later lookups are served from the cache.
+    assert_eq!(
+      client.get_bot(264811613708746752).await.unwrap().username,
+      "Luca"
+    );
+    assert_eq!(fake.requests(), 1);
+  }
+
+  #[tokio::test]
+  async fn never_caches_errors() {
+    let fake = FakeTopgg::start().await.unwrap();
+    let client = fake
+      .client_builder()
+      .cache(CachePolicy::new())
+      .build()
+      .unwrap();
+
+    assert!(client.get_bot(264811613708746752).await.is_err());
+    assert!(client.get_bot(264811613708746752).await.is_err());
+    assert_eq!(fake.requests(), 2);
+  }
+}
@@ -1,10 +1,17 @@
 use crate::{
-  bot::{Bot, IsWeekend},
+  bot::{Bot, GetBots, IsWeekend},
+  cache::ResponseCache,
+  ratelimiter::Ratelimiter,
   user::{User, Voted, Voter},
-  util, Error, Result, Snowflake, Stats,
+  util::{self, api},
+  v1, CachePolicy, Error, Result, RetryPolicy, Stats, TrySnowflake,
 };
-use reqwest::{header, IntoUrl, Method, Response, StatusCode, Version};
+use core::time::Duration;
+use futures_util::stream::{self, Stream, StreamExt, TryStreamExt};
+use reqwest::{header, Method, Proxy, Response, StatusCode, Url, Version};
 use serde::{de::DeserializeOwned, Deserialize};
+use std::collections::{HashMap, HashSet};
+use tokio::time::sleep;
 
 cfg_if::cfg_if! {
   if #[cfg(feature = "autoposter")] {
@@ -18,81 +25,145 @@ cfg_if::cfg_if! {
 }
 
 #[derive(Deserialize)]
-#[serde(rename = "kebab-case")]
 struct Ratelimit {
+  #[serde(alias = "retry-after")]
   retry_after: u16,
 }
 
-macro_rules! api {
-  ($e:literal) => {
-    concat!("https://top.gg/api", $e)
-  };
-
-  ($e:literal, $($rest:tt)*) => {
-    format!(api!($e), $($rest)*)
-  };
-}
+const USER_AGENT: &str = "topgg (https://github.com/top-gg/rust-sdk) Rust";
 
 #[derive(Debug)]
 pub struct InnerClient {
   http: reqwest::Client,
   token: String,
+  pub(crate) base_url: String,
+  timeout: Option<Duration>,
+  user_agent: String,
+  retry: Option<RetryPolicy>,
+  ratelimiter: Option<Ratelimiter>,
+  cache: Option<ResponseCache>,
 }
 
 // this is implemented here because autoposter needs to access this struct from a different thread.
 impl InnerClient {
-  pub(crate) fn new(mut token: String) -> Self {
+  #[inline(always)]
+  pub(crate) fn new(token: String) -> Self {
+    Self::with_http(reqwest::Client::new(), token)
+  }
+
+  pub(crate) fn with_http(http: reqwest::Client, mut token: String) -> Self {
     token.insert_str(0, "Bearer ");
 
     Self {
-      http: reqwest::Client::new(),
+      http,
       token,
+      base_url: String::from(util::DEFAULT_BASE_URL),
+      timeout: None,
+      user_agent: String::from(USER_AGENT),
+      retry: None,
+      ratelimiter: Some(Ratelimiter::new()),
+      cache: None,
     }
   }
 
-  async fn send_inner(&self, method: Method, url: impl IntoUrl, body: Vec<u8>) -> Result<Response> {
-    match self
+  async fn send_once(
+    &self,
+    method: Method,
+    url: &str,
+    body: Vec<u8>,
+    priority: bool,
+  ) -> Result<Response> {
+    if let Some(ratelimiter) = &self.ratelimiter {
+      let bots = url
+        .strip_prefix(&self.base_url)
+        .is_some_and(|path| path.starts_with("/bots"));
+
+      ratelimiter.acquire(bots, priority).await;
+    }
+
+    let mut request = self
       .http
-      .execute(
-        self
-          .http
-          .request(method, url)
-          .header(header::AUTHORIZATION, &self.token)
-          .header(header::CONNECTION, "close")
-          .header(header::CONTENT_LENGTH, body.len())
-          .header(header::CONTENT_TYPE, "application/json")
-          .header(
-            header::USER_AGENT,
-            "topgg (https://github.com/top-gg/rust-sdk) Rust",
-          )
-          .version(Version::HTTP_11)
-          .body(body)
-          .build()
-          .unwrap(),
-      )
-      .await
-    {
-      Ok(response) => {
-        let status = response.status();
-
-        if status.is_success() {
-          Ok(response)
-        } else {
-          Err(match status {
-            StatusCode::UNAUTHORIZED => panic!("Invalid Top.gg API token."),
-            StatusCode::NOT_FOUND => Error::NotFound,
-            StatusCode::TOO_MANY_REQUESTS => match util::parse_json::<Ratelimit>(response).await {
-              Ok(ratelimit) => Error::Ratelimit {
-                retry_after: ratelimit.retry_after,
-              },
-              _ => Error::InternalServerError,
-            },
-            _ => Error::InternalServerError,
-          })
+      .request(method, url)
+      .header(header::AUTHORIZATION, &self.token)
+      .header(header::CONNECTION, "close")
+      .header(header::CONTENT_LENGTH, body.len())
+      .header(header::CONTENT_TYPE, "application/json")
+      .header(header::USER_AGENT, &self.user_agent)
+      .version(Version::HTTP_11)
+      .body(body);
+
+    if let Some(timeout) = self.timeout {
+      request = request.timeout(timeout);
+    }
+
+    let response = self.http.execute(request.build().unwrap()).await?;
+    let status = response.status();
+
+    if status.is_success() {
+      return Ok(response);
+    }
+
+    Err(match status {
+      StatusCode::UNAUTHORIZED => Error::Unauthorized,
+      StatusCode::FORBIDDEN => Error::Forbidden,
+      StatusCode::NOT_FOUND => Error::NotFound,
+      _ => {
+        let headers = response.headers().clone();
+        let body = response.bytes().await?;
+
+        if status == StatusCode::TOO_MANY_REQUESTS {
+          let retry_after = serde_json::from_slice::<Ratelimit>(&body)
+            .ok()
+            .map(|ratelimit| ratelimit.retry_after)
+            .or_else(|| {
+              headers
+                .get(header::RETRY_AFTER)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.parse().ok())
+            });
+
+          if let Some(retry_after) = retry_after {
+            return Err(Error::Ratelimit { retry_after });
+          }
+        }
+
+        Error::Http {
+          status,
+          headers,
+          body: util::truncate_body(&body),
+        }
+      }
+    })
+  }
+
+  async fn send_inner(
+    &self,
+    method: Method,
+    url: impl AsRef<str>,
+    body: Vec<u8>,
+    priority: bool,
+  ) -> Result<Response> {
+    let url = url.as_ref();
+
+    let Some(policy) = &self.retry else {
+      return self.send_once(method, url, body, priority).await;
+    };
+
+    let mut attempt = 1;
+
+    loop {
+      match self
+        .send_once(method.clone(), url, body.clone(), priority)
+        .await
+      {
+        Err(err) if err.is_retryable() && attempt < policy.max_attempts => {
+          sleep(err.retry_after().unwrap_or_else(|| policy.backoff(attempt))).await;
         }
+
+        result => return result,
       }
 
-      Err(err) => Err(Error::InternalClientError(err)),
+      attempt += 1;
     }
   }
 
@@ -100,13 +171,16 @@ impl InnerClient {
   pub(crate) async fn send<T>(
     &self,
     method: Method,
-    url: impl IntoUrl,
+    url: impl AsRef<str>,
     body: Option<Vec<u8>>,
   ) -> Result<T>
   where
     T: DeserializeOwned,
   {
-    match self.send_inner(method, url, body.unwrap_or_default()).await {
+    match self
+      .send_inner(method, url, body.unwrap_or_default(), false)
+      .await
+    {
       Ok(response) => util::parse_json(response).await,
       Err(err) => Err(err),
     }
@@ -116,8 +190,9 @@ impl InnerClient {
     self
       .send_inner(
         Method::POST,
-        api!("/bots/stats"),
+        api!(self, "/bots/stats"),
         serde_json::to_vec(new_stats).unwrap(),
+        true,
       )
       .await
       .map(|_| ())
@@ -145,86 +220,211 @@ impl Client {
     Self { inner }
   }
 
-  /// Fetches a user from a Discord ID.
+  /// Creates a [`ClientBuilder`] for configuring a brand new client instance from a [Top.gg](https://top.gg) token.
   ///
-  /// # Panics
+  /// # Examples
   ///
-  /// Panics if any of the following conditions are met:
-  /// - The ID argument is a string but not numeric
-  /// - The client uses an invalid [Top.gg API](https://docs.top.gg) token (unauthorized)
+  /// Basic usage:
+  ///
+  /// ```rust,no_run
+  /// use core::time::Duration;
+  /// use topgg::Client;
+  ///
+  /// # fn run() -> topgg::Result<()> {
+  /// let client = Client::builder(String::from("my-topgg-token"))
+  ///   .timeout(Duration::from_secs(10))
+  ///   .user_agent_suffix("my-bot/1.0")
+  ///   .build()?;
+  /// # Ok(())
+  /// # }
+  /// ```
+  #[inline(always)]
+  pub fn builder(token: String) -> ClientBuilder {
+    ClientBuilder::new(token)
+  }
+
+  /// Fetches a user from a Discord ID.
+  ///
+  /// If the response cache is enabled (see [`ClientBuilder::cache`]), the user may be returned from the cache instead.
   ///
   /// # Errors
   ///
   /// Errors if any of the following conditions are met:
+  /// - The ID argument is not a valid Discord snowflake/ID, e.g. a string that is not numeric ([`InvalidId`][crate::Error::InvalidId])
   /// - An internal error from the client itself preventing it from sending a HTTP request to [Top.gg](https://top.gg) ([`InternalClientError`][crate::Error::InternalClientError])
-  /// - An unexpected response from the [Top.gg](https://top.gg) servers ([`InternalServerError`][crate::Error::InternalServerError])
+  /// - An unexpected HTTP response from the [Top.gg](https://top.gg) servers ([`Http`][crate::Error::Http])
+  /// - The response from the [Top.gg](https://top.gg) servers cannot be deserialized ([`Deserialize`][crate::Error::Deserialize])
+  /// - The request timed out ([`Timeout`][crate::Error::Timeout])
+  /// - The client uses an invalid [Top.gg API](https://docs.top.gg) token ([`Unauthorized`][crate::Error::Unauthorized])
+  /// - The client's [Top.gg API](https://docs.top.gg) token is not allowed to access the requested resource ([`Forbidden`][crate::Error::Forbidden])
   /// - The requested user does not exist ([`NotFound`][crate::Error::NotFound])
   /// - The client is being ratelimited from sending more HTTP requests ([`Ratelimit`][crate::Error::Ratelimit])
   pub async fn get_user<I>(&self, id: I) -> Result<User>
   where
-    I: Snowflake,
+    I: TrySnowflake,
   {
-    self
-      .inner
-      .send(Method::GET, api!("/users/{}", id.as_snowflake()), None)
-      .await
+    let id = id.try_as_snowflake().ok_or(Error::InvalidId)?;
+    let fetch = || {
+      self
+        .inner
+        .send(Method::GET, api!(self.inner, "/users/{}", id), None)
+    };
+
+    match &self.inner.cache {
+      Some(cache) => cache.users.get_or_fetch(id, fetch).await,
+      None => fetch().await,
+    }
   }
 
   /// Fetches a listed Discord bot from a Discord ID.
   ///
-  /// # Panics
-  ///
-  /// Panics if any of the following conditions are met:
-  /// - The ID argument is a string but not numeric
-  /// - The client uses an invalid [Top.gg API](https://docs.top.gg) token (unauthorized)
+  /// If the response cache is enabled (see [`ClientBuilder::cache`]), the Discord bot may be returned from the cache instead.
   ///
   /// # Errors
   ///
   /// Errors if any of the following conditions are met:
+  /// - The ID argument is not a valid Discord snowflake/ID, e.g. a string that is not numeric ([`InvalidId`][crate::Error::InvalidId])
   /// - An internal error from the client itself preventing it from sending a HTTP request to [Top.gg](https://top.gg) ([`InternalClientError`][crate::Error::InternalClientError])
-  /// - An unexpected response from the [Top.gg](https://top.gg) servers ([`InternalServerError`][crate::Error::InternalServerError])
+  /// - An unexpected HTTP response from the [Top.gg](https://top.gg) servers ([`Http`][crate::Error::Http])
+  /// - The response from the [Top.gg](https://top.gg) servers cannot be deserialized ([`Deserialize`][crate::Error::Deserialize])
+  /// - The request timed out ([`Timeout`][crate::Error::Timeout])
+  /// - The client uses an invalid [Top.gg API](https://docs.top.gg) token ([`Unauthorized`][crate::Error::Unauthorized])
+  /// - The client's [Top.gg API](https://docs.top.gg) token is not allowed to access the requested resource ([`Forbidden`][crate::Error::Forbidden])
   /// - The requested Discord bot is not listed on [Top.gg](https://top.gg) ([`NotFound`][crate::Error::NotFound])
   /// - The client is being ratelimited from sending more HTTP requests ([`Ratelimit`][crate::Error::Ratelimit])
   pub async fn get_bot<I>(&self, id: I) -> Result<Bot>
   where
-    I: Snowflake,
+    I: TrySnowflake,
   {
-    self
-      .inner
-      .send(Method::GET, api!("/bots/{}", id.as_snowflake()), None)
-      .await
+    let id = id.try_as_snowflake().ok_or(Error::InvalidId)?;
+    let fetch = || {
+      self
+        .inner
+        .send(Method::GET, api!(self.inner, "/bots/{}", id), None)
+    };
+
+    match &self.inner.cache {
+      Some(cache) => cache.bots.get_or_fetch(id, fetch).await,
+      None => fetch().await,
+    }
   }
 
-  /// Fetches your Discord bot's statistics.
+  /// Removes a Discord bot from the response cache, so that the next [`get_bot`][Client::get_bot] call fetches it from [Top.gg](https://top.gg) again.
+  ///
+  /// This does nothing if the response cache is disabled (see [`ClientBuilder::cache`]) or if the ID argument is not a valid Discord snowflake/ID.
+  pub fn invalidate_bot<I>(&self, id: I)
+  where
+    I: TrySnowflake,
+  {
+    if let (Some(cache), Some(id)) = (&self.inner.cache, id.try_as_snowflake()) {
+      cache.bots.invalidate(id);
+    }
+  }
+
+  /// Removes a user from the response cache, so that the next [`get_user`][Client::get_user] call fetches it from [Top.gg](https://top.gg) again.
+  ///
+  /// This does nothing if the response cache is disabled (see [`ClientBuilder::cache`]) or if the ID argument is not a valid Discord snowflake/ID.
+  pub fn invalidate_user<I>(&self, id: I)
+  where
+    I: TrySnowflake,
+  {
+    if let (Some(cache), Some(id)) = (&self.inner.cache, id.try_as_snowflake()) {
+      cache.users.invalidate(id);
+    }
+  }
+
+  /// Removes every entry from the response cache. This does nothing if the response cache is disabled (see [`ClientBuilder::cache`]).
+  pub fn clear_cache(&self) {
+    if let Some(cache) = &self.inner.cache {
+      cache.bots.clear();
+      cache.users.clear();
+    }
+  }
+
+  /// Creates a query builder for searching and listing Discord bots listed on [Top.gg](https://top.gg).
+  ///
+  /// The returned [`GetBots`] can either be `.await`ed to fetch a single page, or be turned into a [`Stream`][futures_util::Stream] that fetches every page with [`into_stream`][GetBots::into_stream].
+  ///
+  /// # Examples
+  ///
+  /// Basic usage:
+  ///
+  /// ```rust,no_run
+  /// use topgg::Client;
+  ///
+  /// # async fn run(client: Client) -> topgg::Result<()> {
+  /// let bots = client
+  ///   .get_bots()
+  ///   .search("username", "shiro")
+  ///   .sort_by_votes()
+  ///   .descending()
+  ///   .limit(10)
+  ///   .await?;
+  ///
+  /// println!("{} bots found", bots.total);
+  /// # Ok(())
+  /// # }
+  /// ```
+  #[inline(always)]
+  pub fn get_bots(&self) -> GetBots<'_> {
+    GetBots::new(&self.inner)
+  }
+
+  /// Creates a client view for [Top.gg](https://top.gg)'s v1 project-based API, sharing this client's token, ratelimiter, retry policy and error handling.
   ///
-  /// # Panics
+  /// # Examples
   ///
-  /// Panics if the client uses an invalid [Top.gg API](https://docs.top.gg) token (unauthorized)
+  /// Basic usage:
+  ///
+  /// ```rust,no_run
+  /// use topgg::Client;
+  ///
+  /// # async fn run(client: Client) -> topgg::Result<()> {
+  /// let project = client.v1().get_project().await?;
+  ///
+  /// println!("{} has {} votes this month.", project.name, project.votes);
+  ///
+  /// if let Some(vote) = client.v1().get_vote(661200758510977084u64).await? {
+  ///   println!("voted at {}, can vote again at {}", vote.created_at, vote.expires_at);
+  /// }
+  /// # Ok(())
+  /// # }
+  /// ```
+  #[inline(always)]
+  pub fn v1(&self) -> v1::Client<'_> {
+    v1::Client::new(&self.inner)
+  }
+
+  /// Fetches your Discord bot's statistics.
   ///
   /// # Errors
   ///
   /// Errors if any of the following conditions are met:
   /// - An internal error from the client itself preventing it from sending a HTTP request to [Top.gg](https://top.gg) ([`InternalClientError`][crate::Error::InternalClientError])
-  /// - An unexpected response from the [Top.gg](https://top.gg) servers ([`InternalServerError`][crate::Error::InternalServerError])
+  /// - An unexpected HTTP response from the [Top.gg](https://top.gg) servers ([`Http`][crate::Error::Http])
+  /// - The response from the [Top.gg](https://top.gg) servers cannot be deserialized ([`Deserialize`][crate::Error::Deserialize])
+  /// - The request timed out ([`Timeout`][crate::Error::Timeout])
+  /// - The client uses an invalid [Top.gg API](https://docs.top.gg) token ([`Unauthorized`][crate::Error::Unauthorized])
+  /// - The client's [Top.gg API](https://docs.top.gg) token is not allowed to access the requested resource ([`Forbidden`][crate::Error::Forbidden])
   /// - The client is being ratelimited from sending more HTTP requests ([`Ratelimit`][crate::Error::Ratelimit])
   pub async fn get_stats(&self) -> Result<Stats> {
     self
       .inner
-      .send(Method::GET, api!("/bots/stats"), None)
+      .send(Method::GET, api!(self.inner, "/bots/stats"), None)
       .await
   }
 
   /// Posts your Discord bot's statistics.
   ///
-  /// # Panics
-  ///
-  /// Panics if the client uses an invalid [Top.gg API](https://docs.top.gg) token (unauthorized)
-  ///
   /// # Errors
   ///
   /// Errors if any of the following conditions are met:
   /// - An internal error from the client itself preventing it from sending a HTTP request to [Top.gg](https://top.gg) ([`InternalClientError`][crate::Error::InternalClientError])
-  /// - An unexpected response from the [Top.gg](https://top.gg) servers ([`InternalServerError`][crate::Error::InternalServerError])
+  /// - An unexpected HTTP response from the [Top.gg](https://top.gg) servers ([`Http`][crate::Error::Http])
+  /// - The response from the [Top.gg](https://top.gg) servers cannot be deserialized ([`Deserialize`][crate::Error::Deserialize])
+  /// - The request timed out ([`Timeout`][crate::Error::Timeout])
+  /// - The client uses an invalid [Top.gg API](https://docs.top.gg) token ([`Unauthorized`][crate::Error::Unauthorized])
+  /// - The client's [Top.gg API](https://docs.top.gg) token is not allowed to access the requested resource ([`Forbidden`][crate::Error::Forbidden])
   /// - The client is being ratelimited from sending more HTTP requests ([`Ratelimit`][crate::Error::Ratelimit])
   #[inline(always)]
   pub async fn post_stats(&self, new_stats: Stats) -> Result<()> {
@@ -233,73 +433,355 @@ impl Client {
 
   /// Fetches your Discord bot's last 1000 voters.
   ///
-  /// # Panics
+  /// # Errors
   ///
-  /// Panics if the client uses an invalid [Top.gg API](https://docs.top.gg) token (unauthorized)
+  /// Errors if any of the following conditions are met:
+  /// - An internal error from the client itself preventing it from sending a HTTP request to [Top.gg](https://top.gg) ([`InternalClientError`][crate::Error::InternalClientError])
+  /// - An unexpected HTTP response from the [Top.gg](https://top.gg) servers ([`Http`][crate::Error::Http])
+  /// - The response from the [Top.gg](https://top.gg) servers cannot be deserialized ([`Deserialize`][crate::Error::Deserialize])
+  /// - The request timed out ([`Timeout`][crate::Error::Timeout])
+  /// - The client uses an invalid [Top.gg API](https://docs.top.gg) token ([`Unauthorized`][crate::Error::Unauthorized])
+  /// - The client's [Top.gg API](https://docs.top.gg) token is not allowed to access the requested resource ([`Forbidden`][crate::Error::Forbidden])
+  /// - The client is being ratelimited from sending more HTTP requests ([`Ratelimit`][crate::Error::Ratelimit])
+  pub async fn get_voters(&self) -> Result<Vec<Voter>> {
+    self
+      .inner
+      .send(Method::GET, api!(self.inner, "/bots/votes"), None)
+      .await
+  }
+
+  /// Fetches a single page of your Discord bot's voters, starting from page 1 which contains the most recent voters.
+  ///
+  /// An empty page means that there are no more voters.
   ///
   /// # Errors
   ///
   /// Errors if any of the following conditions are met:
   /// - An internal error from the client itself preventing it from sending a HTTP request to [Top.gg](https://top.gg) ([`InternalClientError`][crate::Error::InternalClientError])
-  /// - An unexpected response from the [Top.gg](https://top.gg) servers ([`InternalServerError`][crate::Error::InternalServerError])
+  /// - An unexpected HTTP response from the [Top.gg](https://top.gg) servers ([`Http`][crate::Error::Http])
+  /// - The response from the [Top.gg](https://top.gg) servers cannot be deserialized ([`Deserialize`][crate::Error::Deserialize])
+  /// - The request timed out ([`Timeout`][crate::Error::Timeout])
+  /// - The client uses an invalid [Top.gg API](https://docs.top.gg) token ([`Unauthorized`][crate::Error::Unauthorized])
+  /// - The client's [Top.gg API](https://docs.top.gg) token is not allowed to access the requested resource ([`Forbidden`][crate::Error::Forbidden])
   /// - The client is being ratelimited from sending more HTTP requests ([`Ratelimit`][crate::Error::Ratelimit])
-  pub async fn get_voters(&self) -> Result<Vec<Voter>> {
+  pub async fn get_voters_page(&self, page: usize) -> Result<Vec<Voter>> {
     self
       .inner
-      .send(Method::GET, api!("/bots/votes"), None)
+      .send(
+        Method::GET,
+        api!(self.inner, "/bots/votes?page={}", page.max(1)),
+        None,
+      )
       .await
   }
 
-  /// Checks if the specified user has voted your Discord bot.
+  /// Creates a [`Stream`] of every one of your Discord bot's voters, walking through every page from [`get_voters_page`][Client::get_voters_page] until an empty page is reached.
+  ///
+  /// The stream ends right after yielding an error.
   ///
-  /// # Panics
+  /// # Examples
   ///
-  /// Panics if any of the following conditions are met:
-  /// - The user ID argument is a string and it's not a valid ID (expected things like `"123456789"`)
-  /// - The client uses an invalid [Top.gg API](https://docs.top.gg) token (unauthorized)
+  /// Basic usage:
+  ///
+  /// ```rust,no_run
+  /// use futures_util::StreamExt;
+  /// use topgg::Client;
+  ///
+  /// # async fn run(client: Client) -> topgg::Result<()> {
+  /// let mut voters = Box::pin(client.voters());
+  ///
+  /// while let Some(voter) = voters.next().await {
+  ///   println!("{:?}", voter?);
+  /// }
+  /// # Ok(())
+  /// # }
+  /// ```
+  pub fn voters(&self) -> impl Stream<Item = Result<Voter>> + '_ {
+    stream::unfold(Some(1), move |page| async move {
+      let page = page?;
+
+      match self.get_voters_page(page).await {
+        Ok(voters) => {
+          let next = if voters.is_empty() {
+            None
+          } else {
+            Some(page + 1)
+          };
+
+          Some((Ok(voters), next))
+        }
+
+        Err(err) => Some((Err(err), None)),
+      }
+    })
+    .map_ok(|voters| stream::iter(voters.into_iter().map(Ok)))
+    .try_flatten()
+  }
+
+  /// Checks if the specified user has voted your Discord bot.
   ///
   /// # Errors
   ///
   /// Errors if any of the following conditions are met:
+  /// - The ID argument is not a valid Discord snowflake/ID, e.g. a string that is not numeric ([`InvalidId`][crate::Error::InvalidId])
   /// - An internal error from the client itself preventing it from sending a HTTP request to [Top.gg](https://top.gg) ([`InternalClientError`][crate::Error::InternalClientError])
-  /// - An unexpected response from the [Top.gg](https://top.gg) servers ([`InternalServerError`][crate::Error::InternalServerError])
+  /// - An unexpected HTTP response from the [Top.gg](https://top.gg) servers ([`Http`][crate::Error::Http])
+  /// - The response from the [Top.gg](https://top.gg) servers cannot be deserialized ([`Deserialize`][crate::Error::Deserialize])
+  /// - The request timed out ([`Timeout`][crate::Error::Timeout])
+  /// - The client uses an invalid [Top.gg API](https://docs.top.gg) token ([`Unauthorized`][crate::Error::Unauthorized])
+  /// - The client's [Top.gg API](https://docs.top.gg) token is not allowed to access the requested resource ([`Forbidden`][crate::Error::Forbidden])
   /// - The client is being ratelimited from sending more HTTP requests ([`Ratelimit`][crate::Error::Ratelimit])
   pub async fn has_voted<I>(&self, user_id: I) -> Result<bool>
   where
-    I: Snowflake,
+    I: TrySnowflake,
   {
+    let user_id = user_id.try_as_snowflake().ok_or(Error::InvalidId)?;
+
     self
       .inner
       .send::<Voted>(
         Method::GET,
-        api!("/bots/check?userId={}", user_id.as_snowflake()),
+        api!(self.inner, "/bots/check?userId={}", user_id),
         None,
       )
       .await
       .map(|res| res.voted != 0)
   }
 
-  /// Checks if the weekend multiplier is active.
+  /// Checks if each of the specified users has voted your Discord bot, running at most `concurrency` checks at once.
+  ///
+  /// Each check goes through the same ratelimiter and retry policy as [`has_voted`][Client::has_voted], and a failed check does not abort the other ones. Duplicate IDs are only checked once.
   ///
-  /// # Panics
+  /// # Examples
   ///
-  /// Panics if the client uses an invalid [Top.gg API](https://docs.top.gg) token (unauthorized)
+  /// Basic usage:
+  ///
+  /// ```rust,no_run
+  /// use topgg::Client;
+  ///
+  /// # async fn run(client: Client) -> topgg::Result<()> {
+  /// let voted = client
+  ///   .has_voted_many([661200758510977084, 264811613708746752], 10)
+  ///   .await?;
+  ///
+  /// for (user_id, result) in voted {
+  ///   match result {
+  ///     Ok(true) => println!("{user_id} has voted"),
+  ///     Ok(false) => println!("{user_id} hasn't voted"),
+  ///     Err(err) => println!("failed to check {user_id}: {err}"),
+  ///   }
+  /// }
+  /// # Ok(())
+  /// # }
+  /// ```
+  ///
+  /// # Errors
+  ///
+  /// Errors if any of the ID arguments is not a valid Discord snowflake/ID, e.g. a string that is not numeric ([`InvalidId`][crate::Error::InvalidId]). No checks are sent in this case.
+  ///
+  /// Errors from each individual check are returned in the map instead. (See [`has_voted`][Client::has_voted])
+  pub async fn has_voted_many<I, S>(
+    &self,
+    user_ids: I,
+    concurrency: usize,
+  ) -> Result<HashMap<u64, Result<bool>>>
+  where
+    I: IntoIterator<Item = S>,
+    S: TrySnowflake,
+  {
+    let user_ids = user_ids
+      .into_iter()
+      .map(|user_id| user_id.try_as_snowflake().ok_or(Error::InvalidId))
+      .collect::<Result<HashSet<_>>>()?;
+
+    Ok(
+      stream::iter(user_ids)
+        .map(|user_id| async move { (user_id, self.has_voted(user_id).await) })
+        .buffer_unordered(concurrency.max(1))
+        .collect()
+        .await,
+    )
+  }
+
+  /// Checks if the weekend multiplier is active.
   ///
   /// # Errors
   ///
   /// Errors if any of the following conditions are met:
   /// - An internal error from the client itself preventing it from sending a HTTP request to [Top.gg](https://top.gg) ([`InternalClientError`][crate::Error::InternalClientError])
-  /// - An unexpected response from the [Top.gg](https://top.gg) servers ([`InternalServerError`][crate::Error::InternalServerError])
+  /// - An unexpected HTTP response from the [Top.gg](https://top.gg) servers ([`Http`][crate::Error::Http])
+  /// - The response from the [Top.gg](https://top.gg) servers cannot be deserialized ([`Deserialize`][crate::Error::Deserialize])
+  /// - The request timed out ([`Timeout`][crate::Error::Timeout])
+  /// - The client uses an invalid [Top.gg API](https://docs.top.gg) token ([`Unauthorized`][crate::Error::Unauthorized])
+  /// - The client's [Top.gg API](https://docs.top.gg) token is not allowed to access the requested resource ([`Forbidden`][crate::Error::Forbidden])
   /// - The client is being ratelimited from sending more HTTP requests ([`Ratelimit`][crate::Error::Ratelimit])
   pub async fn is_weekend(&self) -> Result<bool> {
     self
       .inner
-      .send::<IsWeekend>(Method::GET, api!("/weekend"), None)
+      .send::<IsWeekend>(Method::GET, api!(self.inner, "/weekend"), None)
       .await
       .map(|res| res.is_weekend)
   }
 }
 
+/// A builder for configuring a [`Client`] instance. Created from [`Client::builder`].
+#[must_use]
+#[derive(Debug)]
+pub struct ClientBuilder {
+  token: String,
+  base_url: Option<Url>,
+  timeout: Option<Duration>,
+  connect_timeout: Option<Duration>,
+  proxy: Option<Proxy>,
+  user_agent_suffix: Option<String>,
+  http: Option<reqwest::Client>,
+  retry: Option<RetryPolicy>,
+  ratelimiter: bool,
+  cache: Option<CachePolicy>,
+}
+
+impl ClientBuilder {
+  #[inline(always)]
+  fn new(token: String) -> Self {
+    Self {
+      token,
+      base_url: None,
+      timeout: None,
+      connect_timeout: None,
+      proxy: None,
+      user_agent_suffix: None,
+      http: None,
+      retry: None,
+      ratelimiter: true,
+      cache: None,
+    }
+  }
+
+  /// Sets the base URL every request is sent to. Defaults to `https://top.gg/api`.
+  ///
+  /// This is useful for pointing the client to a local fake server in integration tests.
+  #[inline(always)]
+  pub fn base_url(mut self, base_url: Url) -> Self {
+    self.base_url = Some(base_url);
+    self
+  }
+
+  /// Sets the timeout for each request, from when the request starts connecting until the response body has finished.
+  #[inline(always)]
+  pub fn timeout(mut self, timeout: Duration) -> Self {
+    self.timeout = Some(timeout);
+    self
+  }
+
+  /// Sets the timeout for only the connect phase of each request.
+  ///
+  /// This is ignored if a custom [`reqwest::Client`] is supplied through [`http_client`][ClientBuilder::http_client].
+  #[inline(always)]
+  pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+    self.connect_timeout = Some(timeout);
+    self
+  }
+
+  /// Routes every request through the specified proxy.
+  ///
+  /// This is ignored if a custom [`reqwest::Client`] is supplied through [`http_client`][ClientBuilder::http_client].
+  #[inline(always)]
+  pub fn proxy(mut self, proxy: Proxy) -> Self {
+    self.proxy = Some(proxy);
+    self
+  }
+
+  /// Appends a suffix to the `User-Agent` header sent with every request, e.g. `my-bot/1.0`.
+  #[inline(always)]
+  pub fn user_agent_suffix<S>(mut self, suffix: S) -> Self
+  where
+    S: Into<String>,
+  {
+    self.user_agent_suffix = Some(suffix.into());
+    self
+  }
+
+  /// Uses an existing [`reqwest::Client`] for sending requests instead of creating a new one.
+  #[inline(always)]
+  pub fn http_client(mut self, http: reqwest::Client) -> Self {
+    self.http = Some(http);
+    self
+  }
+
+  /// Automatically retries failed requests according to the specified [`RetryPolicy`]. This is disabled by default.
+  ///
+  /// This applies to every [`Client`] method as well as the [`Autoposter`][crate::Autoposter]'s requests if the [`Autoposter`][crate::Autoposter] is created from this [`Client`].
+  #[inline(always)]
+  pub fn retry(mut self, policy: RetryPolicy) -> Self {
+    self.retry = Some(policy);
+    self
+  }
+
+  /// Sets whether requests should be throttled client-side to stay within [Top.gg](https://top.gg)'s documented ratelimits. This is enabled by default.
+  ///
+  /// Exceeding these ratelimits can get the client blocked for an hour. When enabled, requests exceeding them are queued instead of being sent, and stats posting requests (including the [`Autoposter`][crate::Autoposter]'s) are prioritized over everything else.
+  #[inline(always)]
+  pub fn ratelimiter(mut self, enabled: bool) -> Self {
+    self.ratelimiter = enabled;
+    self
+  }
+
+  /// Caches [`Client::get_bot`] and [`Client::get_user`] responses in memory according to the specified [`CachePolicy`]. This is disabled by default.
+  #[inline(always)]
+  pub fn cache(mut self, policy: CachePolicy) -> Self {
+    self.cache = Some(policy);
+    self
+  }
+
+  /// Builds the [`Client`] instance.
+  ///
+  /// # Errors
+  ///
+  /// Errors if the underlying [`reqwest::Client`] cannot be built, e.g. due to a TLS backend initialization failure. ([`InternalClientError`][crate::Error::InternalClientError])
+  pub fn build(self) -> Result<Client> {
+    let http = match self.http {
+      Some(http) => http,
+      None => {
+        let mut builder = reqwest::Client::builder();
+
+        if let Some(timeout) = self.connect_timeout {
+          builder = builder.connect_timeout(timeout);
+        }
+
+        if let Some(proxy) = self.proxy {
+          builder = builder.proxy(proxy);
+        }
+
+        builder.build().map_err(Error::InternalClientError)?
+      }
+    };
+
+    let mut inner = InnerClient::with_http(http, self.token);
+
+    if let Some(base_url) = self.base_url {
+      inner.base_url = String::from(base_url.as_str().trim_end_matches('/'));
+    }
+
+    if let Some(suffix) = self.user_agent_suffix {
+      inner.user_agent = format!("{} {suffix}", inner.user_agent);
+    }
+
+    inner.timeout = self.timeout;
+    inner.retry = self.retry;
+
+    if !self.ratelimiter {
+      inner.ratelimiter = None;
+    }
+
+    inner.cache = self.cache.map(ResponseCache::new);
+
+    #[cfg(feature = "autoposter")]
+    let inner = Arc::new(inner);
+
+    Ok(Client { inner })
+  }
+}
+
 cfg_if::cfg_if! {
   if #[cfg(feature = "autoposter")] {
     impl autoposter::AsClientSealed for Client {
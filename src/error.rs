@@ -1,4 +1,5 @@
-use core::{fmt, result};
+use core::{fmt, result, time::Duration};
+use reqwest::{header::HeaderMap, StatusCode};
 use std::error;
 
 /// A struct representing an error coming from this SDK - unexpected or not.
@@ -7,12 +8,42 @@ pub enum Error {
   /// An unexpected internal error coming from the client itself, preventing it from sending a request to [Top.gg](https://top.gg).
   InternalClientError(reqwest::Error),
 
-  /// An unexpected error coming from [Top.gg](https://top.gg)'s servers themselves.
-  InternalServerError,
+  /// An unexpected HTTP status code returned from [Top.gg](https://top.gg)'s servers, e.g. a server error (5xx).
+  Http {
+    /// The HTTP status code of the response.
+    status: StatusCode,
+
+    /// The headers of the response.
+    headers: HeaderMap,
+
+    /// The body of the response, truncated to at most 1024 bytes.
+    body: String,
+  },
+
+  /// The response from [Top.gg](https://top.gg)'s servers cannot be deserialized.
+  Deserialize {
+    /// The underlying deserialization error.
+    source: serde_json::Error,
+
+    /// The path to the value that failed to deserialize, e.g. `results[3].owners`.
+    path: String,
+  },
+
+  /// The request timed out before [Top.gg](https://top.gg)'s servers responded.
+  Timeout,
+
+  /// The client uses an invalid or revoked [Top.gg API](https://docs.top.gg) token. (401)
+  Unauthorized,
+
+  /// The client's [Top.gg API](https://docs.top.gg) token is not allowed to access the requested resource. (403)
+  Forbidden,
 
   /// The requested resource does not exist. (404)
   NotFound,
 
+  /// The specified ID is not a valid Discord snowflake/ID, e.g. a string that is not numeric. (See [`TrySnowflake`][crate::TrySnowflake])
+  InvalidId,
+
   /// The client is being ratelimited from sending more HTTP requests.
   Ratelimit {
     /// The amount of seconds before the ratelimit is lifted.
@@ -20,16 +51,56 @@ pub enum Error {
   },
 }
 
+impl Error {
+  /// Whether the request that caused this error can be retried later, i.e. ratelimits, server errors (5xx), connection errors and timeouts.
+  #[must_use]
+  pub fn is_retryable(&self) -> bool {
+    match self {
+      Self::InternalClientError(err) => err.is_connect(),
+      Self::Http { status, .. } => status.is_server_error(),
+      Self::Timeout | Self::Ratelimit { .. } => true,
+      _ => false,
+    }
+  }
+
+  /// The amount of time [Top.gg](https://top.gg) asks the client to wait before sending another request. Only available for [`Ratelimit`][Error::Ratelimit] errors.
+  #[must_use]
+  #[inline(always)]
+  pub fn retry_after(&self) -> Option<Duration> {
+    match self {
+      Self::Ratelimit { retry_after } => Some(Duration::from_secs(*retry_after as _)),
+      _ => None,
+    }
+  }
+}
+
+impl From<reqwest::Error> for Error {
+  #[inline(always)]
+  fn from(err: reqwest::Error) -> Self {
+    if err.is_timeout() {
+      Self::Timeout
+    } else {
+      Self::InternalClientError(err)
+    }
+  }
+}
+
 impl fmt::Display for Error {
   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     match self {
       Self::InternalClientError(err) => write!(f, "internal client error: {err}"),
-      Self::InternalServerError => write!(f, "internal server error"),
+      Self::Http { status, body, .. } => write!(f, "unexpected http status {status}: {body}"),
+      Self::Deserialize { source, path } => {
+        write!(f, "failed to deserialize response at {path}: {source}")
+      }
+      Self::Timeout => write!(f, "request timed out"),
+      Self::Unauthorized => write!(f, "unauthorized"),
+      Self::Forbidden => write!(f, "forbidden"),
       Self::NotFound => write!(f, "not found"),
+      Self::InvalidId => write!(f, "invalid discord snowflake/id"),
       Self::Ratelimit { retry_after } => write!(
         f,
-        "this client is ratelimited, try again in {} seconds",
-        retry_after / 60
+        "this client is ratelimited, try again in {retry_after} seconds"
       ),
     }
   }
@@ -40,6 +111,7 @@ impl error::Error for Error {
   fn source(&self) -> Option<&(dyn error::Error + 'static)> {
     match self {
       Self::InternalClientError(err) => err.source(),
+      Self::Deserialize { source, .. } => Some(source),
       _ => None,
     }
   }
@@ -3,13 +3,19 @@
 
 mod snowflake;
 
+#[cfg(any(feature = "api", feature = "webhook"))]
+mod platform;
+
 cfg_if::cfg_if! {
   if #[cfg(feature = "api")] {
+    mod api;
+    mod cache;
     mod client;
     mod error;
+    mod ratelimiter;
+    mod retry;
     mod util;
 
-    #[cfg(feature = "autoposter")]
     pub(crate) use client::InnerClient;
 
     /// Bot-related traits and structs.
@@ -18,11 +24,20 @@ cfg_if::cfg_if! {
     /// User-related structs.
     pub mod user;
 
+    /// Project-related structs for [Top.gg](https://top.gg)'s v1 API.
+    pub mod project;
+
+    /// A client view for [Top.gg](https://top.gg)'s v1 project-based API.
+    pub mod v1;
+
     #[doc(inline)]
     pub use bot::Stats;
-    pub use client::Client;
+    pub use api::TopggApi;
+    pub use cache::CachePolicy;
+    pub use client::{Client, ClientBuilder};
     pub use error::{Error, Result};
-    pub use snowflake::Snowflake; // for doc purposes
+    pub use retry::RetryPolicy;
+    pub use snowflake::{Snowflake, TrySnowflake}; // for doc purposes
   }
 }
 
@@ -37,6 +52,14 @@ cfg_if::cfg_if! {
   }
 }
 
+cfg_if::cfg_if! {
+  if #[cfg(feature = "testing")] {
+    /// Utilities for testing code that uses this SDK without reaching the real [Top.gg](https://top.gg).
+    #[cfg_attr(docsrs, doc(cfg(feature = "testing")))]
+    pub mod testing;
+  }
+}
+
 cfg_if::cfg_if! {
   if #[cfg(feature = "webhook")] {
     mod webhook;
@@ -44,3 +67,11 @@ cfg_if::cfg_if! {
     pub use webhook::*;
   }
 }
+
+cfg_if::cfg_if! {
+  if #[cfg(all(feature = "api", feature = "webhook"))] {
+    mod vote_cache;
+
+    pub use vote_cache::VoteCache;
+  }
+}
@@ -0,0 +1,30 @@
+use serde::Deserialize;
+
+/// The type of a project listed on [Top.gg](https://top.gg).
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum ProjectType {
+  /// A Discord bot.
+  Bot,
+
+  /// A Discord server.
+  Server,
+
+  /// A project type that isn't known by this SDK yet.
+  #[serde(other)]
+  Unknown,
+}
+
+/// The platform of a project listed on [Top.gg](https://top.gg).
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum Platform {
+  /// Discord.
+  Discord,
+
+  /// A platform that isn't known by this SDK yet.
+  #[serde(other)]
+  Unknown,
+}
@@ -0,0 +1,116 @@
+use crate::{snowflake, util};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::{collections::HashMap, time::SystemTime};
+
+pub use crate::platform::{Platform, ProjectType};
+
+/// A struct representing a project listed on [Top.gg](https://top.gg), such as a Discord bot or a Discord server. (See [`v1::Client::get_project`][crate::v1::Client::get_project])
+#[must_use]
+#[derive(Clone, Debug, Deserialize)]
+pub struct Project {
+  /// The [Top.gg](https://top.gg) ID of this project.
+  pub id: String,
+
+  /// The name of this project.
+  pub name: String,
+
+  /// The type of this project.
+  #[serde(rename = "type")]
+  pub project_type: ProjectType,
+
+  /// The platform of this project.
+  pub platform: Platform,
+
+  /// The short description of this project.
+  #[serde(default, deserialize_with = "util::deserialize_optional_string")]
+  pub headline: Option<String>,
+
+  /// The tags of this project.
+  #[serde(default, deserialize_with = "util::deserialize_default")]
+  pub tags: Vec<String>,
+
+  /// The amount of votes this project has this month.
+  #[serde(default, deserialize_with = "util::deserialize_default")]
+  pub votes: u64,
+
+  /// The amount of votes this project has in total.
+  #[serde(default, deserialize_with = "util::deserialize_default")]
+  pub votes_total: u64,
+
+  /// The average review score of this project.
+  #[serde(default, deserialize_with = "util::deserialize_default")]
+  pub review_score: f64,
+
+  /// The amount of reviews this project has.
+  #[serde(default, deserialize_with = "util::deserialize_default")]
+  pub review_count: u64,
+
+  /// Any other fields returned by [Top.gg](https://top.gg) for this project that aren't covered by this struct.
+  #[serde(flatten)]
+  pub extra: HashMap<String, serde_json::Value>,
+}
+
+/// A struct representing a vote on a [Top.gg](https://top.gg) [`Project`]. (See [`v1::Client::get_votes`][crate::v1::Client::get_votes])
+#[must_use]
+#[derive(Clone, Debug, Deserialize)]
+pub struct ProjectVote {
+  /// The [Top.gg](https://top.gg) ID of the user who voted.
+  pub user_id: String,
+
+  /// The Discord ID of the user who voted.
+  #[serde(deserialize_with = "snowflake::deserialize")]
+  pub platform_id: u64,
+
+  /// How many votes this vote counts as, e.g. `2` during the weekend multiplier.
+  #[serde(default = "default_weight")]
+  pub weight: u32,
+
+  /// When this vote was created.
+  pub created_at: DateTime<Utc>,
+
+  /// When this vote expires and the user can vote again.
+  pub expires_at: DateTime<Utc>,
+}
+
+/// A single page of votes on a [Top.gg](https://top.gg) [`Project`]. (See [`v1::Client::get_votes`][crate::v1::Client::get_votes])
+#[must_use]
+#[derive(Clone, Debug, Deserialize)]
+pub struct ProjectVotes {
+  /// The votes on this page.
+  #[serde(default, deserialize_with = "util::deserialize_default")]
+  pub data: Vec<ProjectVote>,
+
+  /// The cursor for requesting the next page, if any. (See [`v1::Client::get_votes_after`][crate::v1::Client::get_votes_after])
+  #[serde(default, deserialize_with = "util::deserialize_optional_string")]
+  pub cursor: Option<String>,
+}
+
+/// A struct representing a user's latest vote on a [Top.gg](https://top.gg) [`Project`]. (See [`v1::Client::get_vote`][crate::v1::Client::get_vote])
+#[must_use]
+#[derive(Clone, Debug, Deserialize)]
+pub struct VoteStatus {
+  /// How many votes this vote counts as, e.g. `2` during the weekend multiplier.
+  #[serde(default = "default_weight")]
+  pub weight: u32,
+
+  /// When this vote was created.
+  pub created_at: DateTime<Utc>,
+
+  /// When this vote expires and the user can vote again.
+  pub expires_at: DateTime<Utc>,
+}
+
+impl VoteStatus {
+  /// Checks if this vote hasn't expired yet.
+  #[must_use]
+  #[inline(always)]
+  pub fn is_active(&self) -> bool {
+    self.expires_at > DateTime::<Utc>::from(SystemTime::now())
+  }
+}
+
+#[inline(always)]
+const fn default_weight() -> u32 {
+  1
+}
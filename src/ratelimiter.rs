@@ -0,0 +1,198 @@
+use core::{fmt, time::Duration};
+use std::{sync::Mutex, time::Instant};
+use tokio::time::sleep;
+
+/// The bucket every request counts towards. (100 requests per second)
+const GLOBAL: (u32, Duration) = (100, Duration::from_secs(1));
+
+/// The bucket every request to the `/bots/*` routes counts towards. (60 requests per minute)
+const BOTS: (u32, Duration) = (60, Duration::from_secs(60));
+
+struct Bucket {
+  rate: f64,
+  capacity: f64,
+  tokens: f64,
+  updated: Instant,
+}
+
+impl Bucket {
+  fn new(capacity: u32, period: Duration) -> Self {
+    let capacity = capacity as f64;
+
+    Self {
+      rate: capacity / period.as_secs_f64(),
+      capacity,
+      tokens: capacity,
+      updated: Instant::now(),
+    }
+  }
+
+  fn refill(&mut self, now: Instant) {
+    let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+
+    self.tokens = (self.tokens + elapsed * self.rate).min(self.capacity);
+    self.updated = now;
+  }
+
+  // takes a token from this bucket, and returns how long the caller has to wait before it's allowed to use it.
+  // tokens can go negative, which means that the caller is queued behind the other callers.
+  fn reserve(&mut self, now: Instant) -> Duration {
+    self.refill(now);
+    self.tokens -= 1.0;
+
+    if self.tokens >= 0.0 {
+      Duration::ZERO
+    } else {
+      Duration::from_secs_f64(-self.tokens / self.rate)
+    }
+  }
+}
+
+struct Lane {
+  global: Bucket,
+  bots: Bucket,
+}
+
+impl Lane {
+  fn new(share: impl Fn(u32) -> u32) -> Self {
+    Self {
+      global: Bucket::new(share(GLOBAL.0), GLOBAL.1),
+      bots: Bucket::new(share(BOTS.0), BOTS.1),
+    }
+  }
+
+  // takes a token from this lane only if it can be used right away.
+  fn try_reserve(&mut self, bots: bool, now: Instant) -> bool {
+    self.global.refill(now);
+    self.bots.refill(now);
+
+    if self.global.tokens < 1.0 || (bots && self.bots.tokens < 1.0) {
+      return false;
+    }
+
+    self.global.tokens -= 1.0;
+
+    if bots {
+      self.bots.tokens -= 1.0;
+    }
+
+    true
+  }
+
+  fn reserve(&mut self, bots: bool, now: Instant) -> Duration {
+    let wait = self.global.reserve(now);
+
+    if bots {
+      wait.max(self.bots.reserve(now))
+    } else {
+      wait
+    }
+  }
+}
+
+/// A client-side token bucket ratelimiter following [Top.gg](https://top.gg)'s documented limits.
+///
+/// Requests are never rejected, they are queued in the order they arrived instead. One request per bucket period is reserved for priority requests (e.g. posting bot statistics), so that they can never be starved by a flood of other requests. Priority requests only use that reserved request when the other requests have none left.
+pub(crate) struct Ratelimiter {
+  normal: Mutex<Lane>,
+  priority: Mutex<Lane>,
+}
+
+impl Ratelimiter {
+  pub(crate) fn new() -> Self {
+    Self {
+      normal: Mutex::new(Lane::new(|capacity| capacity - 1)),
+      priority: Mutex::new(Lane::new(|_| 1)),
+    }
+  }
+
+  /// Returns how long the caller has to wait before it's allowed to send a request. Priority requests borrow from the normal lane if it can be used right away.
+  fn reserve(&self, bots: bool, priority: bool, now: Instant) -> Duration {
+    if !priority {
+      return self.normal.lock().unwrap().reserve(bots, now);
+    }
+
+    if self.normal.lock().unwrap().try_reserve(bots, now) {
+      Duration::ZERO
+    } else {
+      self.priority.lock().unwrap().reserve(bots, now)
+    }
+  }
+
+  /// Waits until the caller is allowed to send a request.
+  pub(crate) async fn acquire(&self, bots: bool, priority: bool) {
+    let wait = self.reserve(bots, priority, Instant::now());
+
+    if !wait.is_zero() {
+      sleep(wait).await;
+    }
+  }
+}
+
+impl fmt::Debug for Ratelimiter {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("Ratelimiter").finish_non_exhaustive()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn bucket_queues_callers() {
+    let now = Instant::now();
+    let mut bucket = Bucket::new(2, Duration::from_secs(2));
+
+    assert_eq!(bucket.reserve(now), Duration::ZERO);
+    assert_eq!(bucket.reserve(now), Duration::ZERO);
+    assert_eq!(bucket.reserve(now), Duration::from_secs(1));
+    assert_eq!(bucket.reserve(now), Duration::from_secs(2));
+
+    // refilled tokens go to the callers queued first.
+    assert_eq!(
+      bucket.reserve(now + Duration::from_secs(2)),
+      Duration::from_secs(1)
+    );
+  }
+
+  #[test]
+  fn bucket_refills_up_to_capacity() {
+    let now = Instant::now();
+    let mut bucket = Bucket::new(2, Duration::from_secs(2));
+
+    bucket.reserve(now);
+    bucket.reserve(now);
+
+    let later = now + Duration::from_secs(60);
+
+    assert_eq!(bucket.reserve(later), Duration::ZERO);
+    assert_eq!(bucket.reserve(later), Duration::ZERO);
+    assert_eq!(bucket.reserve(later), Duration::from_secs(1));
+  }
+
+  #[test]
+  fn priority_requests_borrow_from_idle_normal_lane() {
+    let now = Instant::now();
+    let ratelimiter = Ratelimiter::new();
+
+    // these would have to wait for the single reserved /bots/* request per minute if they didn't borrow.
+    for _ in 0..3 {
+      assert_eq!(ratelimiter.reserve(true, true, now), Duration::ZERO);
+    }
+  }
+
+  #[test]
+  fn priority_requests_are_not_starved() {
+    let now = Instant::now();
+    let ratelimiter = Ratelimiter::new();
+
+    for _ in 0..BOTS.0 - 1 {
+      ratelimiter.reserve(true, false, now);
+    }
+
+    assert!(!ratelimiter.reserve(true, false, now).is_zero());
+    assert_eq!(ratelimiter.reserve(true, true, now), Duration::ZERO);
+    assert!(!ratelimiter.reserve(true, true, now).is_zero());
+  }
+}
@@ -0,0 +1,125 @@
+use core::{
+  hash::{BuildHasher, Hasher},
+  time::Duration,
+};
+use std::{collections::hash_map::RandomState, time::SystemTime};
+
+/// A struct representing a policy for automatically retrying failed requests. (See [`ClientBuilder::retry`][crate::ClientBuilder::retry])
+///
+/// - Requests that got ratelimited (429) are retried after exactly the amount of time [Top.gg](https://top.gg) asks the client to wait.
+/// - Requests that failed due to a server error (5xx), a connection error or a timeout are retried with exponential backoff and full jitter.
+/// - Other errors are never retried.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust,no_run
+/// use core::time::Duration;
+/// use topgg::{Client, RetryPolicy};
+///
+/// # fn run() -> topgg::Result<()> {
+/// let client = Client::builder(String::from("my-topgg-token"))
+///   .retry(RetryPolicy::new(5).base_delay(Duration::from_secs(1)))
+///   .build()?;
+/// # Ok(())
+/// # }
+/// ```
+#[must_use]
+#[derive(Clone, Debug)]
+pub struct RetryPolicy {
+  pub(crate) max_attempts: usize,
+  base_delay: Duration,
+  max_delay: Duration,
+}
+
+impl RetryPolicy {
+  /// Creates a [`RetryPolicy`] that sends a request at most `max_attempts` times (including the first attempt), with a base backoff delay of 500 milliseconds capped at 30 seconds.
+  pub const fn new(max_attempts: usize) -> Self {
+    Self {
+      max_attempts,
+      base_delay: Duration::from_millis(500),
+      max_delay: Duration::from_secs(30),
+    }
+  }
+
+  /// Sets the backoff delay before the first retry. Each subsequent retry doubles this delay.
+  #[inline(always)]
+  pub const fn base_delay(mut self, delay: Duration) -> Self {
+    self.base_delay = delay;
+    self
+  }
+
+  /// Sets the maximum backoff delay between retries. This does not apply to ratelimits.
+  #[inline(always)]
+  pub const fn max_delay(mut self, delay: Duration) -> Self {
+    self.max_delay = delay;
+    self
+  }
+
+  /// Computes the backoff delay after the specified (one-based) attempt failed, with full jitter applied.
+  pub(crate) fn backoff(&self, attempt: usize) -> Duration {
+    let exponent = attempt.saturating_sub(1).min(31) as u32;
+    let ceiling = self
+      .base_delay
+      .saturating_mul(1 << exponent)
+      .min(self.max_delay);
+
+    ceiling.mul_f64(jitter())
+  }
+}
+
+/// Retries a failed request up to 3 times in total.
+impl Default for RetryPolicy {
+  #[inline(always)]
+  fn default() -> Self {
+    Self::new(3)
+  }
+}
+
+// returns a pseudo-random number in the range of [0, 1).
+fn jitter() -> f64 {
+  let mut hasher = RandomState::new().build_hasher();
+
+  if let Ok(now) = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH) {
+    hasher.write_u128(now.as_nanos());
+  }
+
+  (hasher.finish() >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn backoff_stays_below_max_delay() {
+    let policy = RetryPolicy::new(100)
+      .base_delay(Duration::from_secs(1))
+      .max_delay(Duration::from_secs(10));
+
+    for attempt in 1..100 {
+      assert!(policy.backoff(attempt) <= Duration::from_secs(10));
+    }
+  }
+
+  #[test]
+  fn backoff_ceiling_doubles_per_attempt() {
+    let policy = RetryPolicy::new(5).base_delay(Duration::from_secs(1));
+
+    for attempt in 1..5 {
+      let ceiling = Duration::from_secs(1 << (attempt - 1));
+
+      for _ in 0..100 {
+        assert!(policy.backoff(attempt) < ceiling);
+      }
+    }
+  }
+
+  #[test]
+  fn jitter_is_in_range() {
+    for _ in 0..1000 {
+      assert!((0.0..1.0).contains(&jitter()));
+    }
+  }
+}
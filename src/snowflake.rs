@@ -38,16 +38,46 @@ macro_rules! impl_snowflake(
 
 impl_snowflake!(self, u64, *self);
 
-macro_rules! impl_string(
-  ($($t:ty),+) => {$(
-    impl_snowflake!(self, $t, (*self).parse().expect("invalid snowflake as it's not numeric"));
-  )+}
-);
-
-impl_string!(&str, String);
-
 cfg_if::cfg_if! {
   if #[cfg(feature = "api")] {
+    /// A trait that represents any datatype that can *possibly* be interpreted as a Discord snowflake/ID, such as user-provided strings.
+    ///
+    /// Every datatype implementing [`Snowflake`] implements this trait as well.
+    pub trait TrySnowflake {
+      /// The method that attempts to convert this value to a [`u64`]. Returns `None` if this value is not a valid Discord snowflake/ID.
+      fn try_as_snowflake(&self) -> Option<u64>;
+    }
+
+    impl<T> TrySnowflake for T
+    where
+      T: Snowflake,
+    {
+      #[inline(always)]
+      fn try_as_snowflake(&self) -> Option<u64> {
+        Some(self.as_snowflake())
+      }
+    }
+
+    macro_rules! impl_try_snowflake(
+      ($(#[$attr:meta] )?$self:ident,$t:ty,$body:expr) => {
+        $(#[$attr])?
+        impl TrySnowflake for $t {
+          #[inline(always)]
+          fn try_as_snowflake(&$self) -> Option<u64> {
+            $body
+          }
+        }
+      }
+    );
+
+    macro_rules! impl_string(
+      ($($t:ty),+) => {$(
+        impl_try_snowflake!(self, $t, (*self).parse().ok());
+      )+}
+    );
+
+    impl_string!(&str, String);
+
     macro_rules! impl_topgg_idstruct(
       ($($t:ty),+) => {$(
         impl_snowflake!(self, &$t, (*self).id);
@@ -70,10 +100,11 @@ cfg_if::cfg_if! {
       (*self).user.id.get()
     );
 
-    impl_snowflake!(
+    #[cfg(feature = "api")]
+    impl_try_snowflake!(
       #[cfg_attr(docsrs, doc(cfg(feature = "serenity")))] self,
       &serenity::model::guild::PartialMember,
-      (*self).user.as_ref().expect("user property in PartialMember is None").id.get()
+      self.user.as_ref().map(|user| user.id.get())
     );
 
     macro_rules! impl_serenity_id(
@@ -0,0 +1,221 @@
+use crate::{
+  bot::{Bot, Stats},
+  user::{User, Voter},
+  Error, Result, TopggApi,
+};
+use std::sync::Mutex;
+
+type Responder<A, T> = Box<dyn Fn(A) -> Result<T> + Send + Sync>;
+
+/// A call recorded by a [`MockApi`].
+#[derive(Clone, Debug)]
+pub enum Call {
+  /// A [`get_user`][TopggApi::get_user] call with the specified user ID.
+  GetUser(u64),
+
+  /// A [`get_bot`][TopggApi::get_bot] call with the specified Discord bot ID.
+  GetBot(u64),
+
+  /// A [`get_stats`][TopggApi::get_stats] call.
+  GetStats,
+
+  /// A [`post_stats`][TopggApi::post_stats] call with the specified statistics.
+  PostStats(Stats),
+
+  /// A [`get_voters`][TopggApi::get_voters] call.
+  GetVoters,
+
+  /// A [`has_voted`][TopggApi::has_voted] call with the specified user ID.
+  HasVoted(u64),
+
+  /// An [`is_weekend`][TopggApi::is_weekend] call.
+  IsWeekend,
+}
+
+/// A programmable in-memory [`TopggApi`] implementation that records every call made to it, for unit-testing code that takes a [`TopggApi`].
+///
+/// Unless programmed otherwise:
+/// - [`get_user`][TopggApi::get_user] and [`get_bot`][TopggApi::get_bot] error with [`NotFound`][Error::NotFound].
+/// - [`get_stats`][TopggApi::get_stats] returns the last posted statistics, or zero server count if none are posted yet.
+/// - [`post_stats`][TopggApi::post_stats] succeeds.
+/// - [`get_voters`][TopggApi::get_voters] returns no voters.
+/// - [`has_voted`][TopggApi::has_voted] and [`is_weekend`][TopggApi::is_weekend] return `false`.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust,no_run
+/// use topgg::{testing::{Call, MockApi}, TopggApi};
+///
+/// # async fn run() {
+/// let mock = MockApi::new().on_has_voted(|user_id| Ok(user_id == 661200758510977084));
+///
+/// assert!(mock.has_voted(661200758510977084).await.unwrap());
+/// assert!(!mock.has_voted(264811613708746752).await.unwrap());
+///
+/// assert!(matches!(
+///   mock.calls().as_slice(),
+///   [Call::HasVoted(661200758510977084), Call::HasVoted(264811613708746752)]
+/// ));
+/// # }
+/// ```
+#[must_use]
+#[derive(Default)]
+pub struct MockApi {
+  get_user: Option<Responder<u64, User>>,
+  get_bot: Option<Responder<u64, Bot>>,
+  get_stats: Option<Responder<(), Stats>>,
+  post_stats: Option<Responder<Stats, ()>>,
+  get_voters: Option<Responder<(), Vec<Voter>>>,
+  has_voted: Option<Responder<u64, bool>>,
+  is_weekend: Option<Responder<(), bool>>,
+  calls: Mutex<Vec<Call>>,
+}
+
+macro_rules! responders {
+  ($(
+    $(#[$attr:meta])*
+    $method:ident: $field:ident($arg:ty) -> $output:ty;
+  )*) => {
+    impl MockApi {
+      /// Creates a brand new [`MockApi`] with the default responses and no recorded calls.
+      #[inline(always)]
+      pub fn new() -> Self {
+        Self::default()
+      }
+
+      $(
+        $(#[$attr])*
+        pub fn $method<F>(mut self, responder: F) -> Self
+        where
+          F: Fn($arg) -> Result<$output> + Send + Sync + 'static,
+        {
+          self.$field = Some(Box::new(responder));
+          self
+        }
+      )*
+    }
+  };
+}
+
+responders! {
+  /// Programs the response of [`get_user`][TopggApi::get_user] from the requested user ID.
+  on_get_user: get_user(u64) -> User;
+
+  /// Programs the response of [`get_bot`][TopggApi::get_bot] from the requested Discord bot ID.
+  on_get_bot: get_bot(u64) -> Bot;
+
+  /// Programs the response of [`get_stats`][TopggApi::get_stats].
+  on_get_stats: get_stats(()) -> Stats;
+
+  /// Programs the response of [`post_stats`][TopggApi::post_stats] from the posted statistics.
+  on_post_stats: post_stats(Stats) -> ();
+
+  /// Programs the response of [`get_voters`][TopggApi::get_voters].
+  on_get_voters: get_voters(()) -> Vec<Voter>;
+
+  /// Programs the response of [`has_voted`][TopggApi::has_voted] from the requested user ID.
+  on_has_voted: has_voted(u64) -> bool;
+
+  /// Programs the response of [`is_weekend`][TopggApi::is_weekend].
+  on_is_weekend: is_weekend(()) -> bool;
+}
+
+impl MockApi {
+  /// Retrieves every call made to this [`MockApi`] so far, in order.
+  #[must_use]
+  #[inline(always)]
+  pub fn calls(&self) -> Vec<Call> {
+    self.calls.lock().unwrap().clone()
+  }
+
+  /// Forgets every call made to this [`MockApi`] so far.
+  #[inline(always)]
+  pub fn clear_calls(&self) {
+    self.calls.lock().unwrap().clear();
+  }
+
+  #[inline(always)]
+  fn record(&self, call: Call) {
+    self.calls.lock().unwrap().push(call);
+  }
+}
+
+#[async_trait::async_trait]
+impl TopggApi for MockApi {
+  async fn get_user(&self, id: u64) -> Result<User> {
+    self.record(Call::GetUser(id));
+
+    match &self.get_user {
+      Some(responder) => responder(id),
+      None => Err(Error::NotFound),
+    }
+  }
+
+  async fn get_bot(&self, id: u64) -> Result<Bot> {
+    self.record(Call::GetBot(id));
+
+    match &self.get_bot {
+      Some(responder) => responder(id),
+      None => Err(Error::NotFound),
+    }
+  }
+
+  async fn get_stats(&self) -> Result<Stats> {
+    self.record(Call::GetStats);
+
+    if let Some(responder) = &self.get_stats {
+      return responder(());
+    }
+
+    let calls = self.calls.lock().unwrap();
+
+    Ok(
+      calls
+        .iter()
+        .rev()
+        .find_map(|call| match call {
+          Call::PostStats(stats) => Some(stats.clone()),
+          _ => None,
+        })
+        .unwrap_or_else(|| Stats::from(0)),
+    )
+  }
+
+  async fn post_stats(&self, new_stats: Stats) -> Result<()> {
+    self.record(Call::PostStats(new_stats.clone()));
+
+    match &self.post_stats {
+      Some(responder) => responder(new_stats),
+      None => Ok(()),
+    }
+  }
+
+  async fn get_voters(&self) -> Result<Vec<Voter>> {
+    self.record(Call::GetVoters);
+
+    match &self.get_voters {
+      Some(responder) => responder(()),
+      None => Ok(Vec::new()),
+    }
+  }
+
+  async fn has_voted(&self, user_id: u64) -> Result<bool> {
+    self.record(Call::HasVoted(user_id));
+
+    match &self.has_voted {
+      Some(responder) => responder(user_id),
+      None => Ok(false),
+    }
+  }
+
+  async fn is_weekend(&self) -> Result<bool> {
+    self.record(Call::IsWeekend);
+
+    match &self.is_weekend {
+      Some(responder) => responder(()),
+      None => Ok(false),
+    }
+  }
+}
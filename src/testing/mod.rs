@@ -0,0 +1,5 @@
+mod mock;
+mod server;
+
+pub use mock::{Call, MockApi};
+pub use server::{Failure, FakeTopgg};
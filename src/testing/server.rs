@@ -0,0 +1,523 @@
+use crate::{Client, ClientBuilder, Stats};
+use core::{convert::Infallible, time::Duration};
+use http_body_util::{BodyExt, Full};
+use hyper::{
+  body::{Bytes, Incoming},
+  header,
+  server::conn::http1,
+  service::service_fn,
+  Method, Request, Response, StatusCode,
+};
+use hyper_util::rt::TokioIo;
+use reqwest::Url;
+use serde_json::{json, Value};
+use std::{
+  collections::{HashMap, HashSet, VecDeque},
+  io,
+  net::SocketAddr,
+  sync::{Arc, Mutex},
+};
+use tokio::{
+  net::TcpListener,
+  task::{spawn, JoinHandle, JoinSet},
+  time::sleep,
+};
+
+/// The amount of voters in a single page of [`Client::get_voters_page`].
+const VOTERS_PER_PAGE: usize = 100;
+
+/// How long to wait before accepting connections again after failing to, e.g. after running out of file descriptors.
+const ACCEPT_BACKOFF: Duration = Duration::from_millis(100);
+
+/// A failure to be injected into the next response of a [`FakeTopgg`] server. (See [`FakeTopgg::inject`])
+#[derive(Clone, Debug)]
+pub enum Failure {
+  /// Responds with the specified HTTP status code and an empty JSON object, e.g. `500` for a server error.
+  Status(u16),
+
+  /// Responds with a ratelimit (429) that is lifted after the specified amount of seconds.
+  Ratelimit(u16),
+}
+
+#[derive(Default)]
+struct State {
+  token: String,
+  bots: Vec<Value>,
+  users: HashMap<String, Value>,
+  voters: Vec<Value>,
+  votes: HashSet<u64>,
+  is_weekend: bool,
+  stats: Option<Stats>,
+  failures: VecDeque<Failure>,
+  requests: usize,
+}
+
+/// An in-process fake [Top.gg API](https://docs.top.gg) server, for testing code that uses a [`Client`] without reaching the real [Top.gg](https://top.gg).
+///
+/// It serves every endpoint used by [`Client`] from seedable in-memory data, validates the token sent by the client, and can inject ratelimits and server errors.
+///
+/// **NOTE:** This struct owns the task that runs the server. The server will stop once this struct is dropped.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust,no_run
+/// use serde_json::json;
+/// use topgg::{testing::FakeTopgg, Stats};
+///
+/// # async fn run() {
+/// let fake = FakeTopgg::start().await.unwrap();
+///
+/// fake.add_vote(661200758510977084);
+/// fake.add_bot(json!({
+///   "id": "264811613708746752",
+///   "username": "Luca",
+///   "discriminator": "1375",
+///   "prefix": "- or @Luca",
+///   "shortdesc": "Luca is a bot for managing and informing members of the server",
+///   "owners": ["129908908096487424"],
+///   "date": "2017-04-26T18:08:17.125Z",
+///   "certifiedBot": false,
+///   "points": 397,
+///   "monthlyPoints": 19,
+/// }));
+///
+/// let client = fake.client();
+///
+/// assert!(client.has_voted(661200758510977084).await.unwrap());
+/// assert_eq!(client.get_bot(264811613708746752).await.unwrap().username, "Luca");
+///
+/// client.post_stats(Stats::from(12345)).await.unwrap();
+/// assert_eq!(fake.stats().unwrap().server_count(), Some(12345));
+/// # }
+/// ```
+#[must_use]
+pub struct FakeTopgg {
+  addr: SocketAddr,
+  state: Arc<Mutex<State>>,
+  task: JoinHandle<()>,
+}
+
+impl FakeTopgg {
+  /// The [Top.gg API](https://docs.top.gg) token accepted by servers created from [`start`][FakeTopgg::start].
+  pub const TOKEN: &'static str = "fake-topgg-token";
+
+  /// Starts a brand new and empty fake server on a random local port, accepting [`TOKEN`][FakeTopgg::TOKEN] as its only valid token.
+  ///
+  /// # Errors
+  ///
+  /// Errors if the server cannot bind to a local port.
+  #[inline(always)]
+  pub async fn start() -> io::Result<Self> {
+    Self::start_with_token(Self::TOKEN).await
+  }
+
+  /// Starts a brand new and empty fake server on a random local port, accepting the specified token as its only valid token.
+  ///
+  /// # Errors
+  ///
+  /// Errors if the server cannot bind to a local port.
+  pub async fn start_with_token<T>(token: T) -> io::Result<Self>
+  where
+    T: Into<String>,
+  {
+    let listener = TcpListener::bind(("127.0.0.1", 0)).await?;
+    let addr = listener.local_addr()?;
+    let state = Arc::new(Mutex::new(State {
+      token: format!("Bearer {}", token.into()),
+      ..State::default()
+    }));
+
+    let server_state = Arc::clone(&state);
+
+    Ok(Self {
+      addr,
+      state,
+      task: spawn(async move {
+        // aborted along with this task once the server is dropped.
+        let mut connections = JoinSet::new();
+
+        loop {
+          let accepted = listener.accept().await;
+
+          while connections.try_join_next().is_some() {}
+
+          let Ok((stream, _)) = accepted else {
+            sleep(ACCEPT_BACKOFF).await;
+            continue;
+          };
+
+          let state = Arc::clone(&server_state);
+
+          connections.spawn(async move {
+            let service = service_fn(move |request| {
+              let state = Arc::clone(&state);
+
+              async move { Ok::<_, Infallible>(handle(&state, request).await) }
+            });
+
+            let _ = http1::Builder::new()
+              .serve_connection(TokioIo::new(stream), service)
+              .await;
+          });
+        }
+      }),
+    })
+  }
+
+  /// The base URL of this fake server, to be passed to [`ClientBuilder::base_url`].
+  #[must_use]
+  pub fn url(&self) -> Url {
+    Url::parse(&format!("http://{}", self.addr)).unwrap()
+  }
+
+  /// Creates a [`ClientBuilder`] already pointed to this fake server with a valid token, for further configuration.
+  ///
+  /// Client-side ratelimiting is disabled so that tests don't have to wait for it.
+  pub fn client_builder(&self) -> ClientBuilder {
+    let token = self.state.lock().unwrap().token["Bearer ".len()..].to_owned();
+
+    Client::builder(token)
+      .base_url(self.url())
+      .ratelimiter(false)
+  }
+
+  /// Creates a [`Client`] already pointed to this fake server with a valid token.
+  #[inline(always)]
+  pub fn client(&self) -> Client {
+    self.client_builder().build().unwrap()
+  }
+
+  /// Adds a Discord bot to this fake server, in the same JSON format as [Top.gg](https://top.gg)'s.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the JSON value doesn't have a string `id` property.
+  pub fn add_bot(&self, bot: Value) {
+    assert!(bot["id"].is_string(), "bot JSON must have a string id.");
+
+    self.state.lock().unwrap().bots.push(bot);
+  }
+
+  /// Adds a user to this fake server, in the same JSON format as [Top.gg](https://top.gg)'s.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the JSON value doesn't have a string `id` property.
+  pub fn add_user(&self, user: Value) {
+    let id = user["id"]
+      .as_str()
+      .expect("user JSON must have a string id.")
+      .to_owned();
+
+    self.state.lock().unwrap().users.insert(id, user);
+  }
+
+  /// Adds a voter to this fake server, in the same JSON format as [Top.gg](https://top.gg)'s. The most recent voter should be added last.
+  ///
+  /// This does not affect [`Client::has_voted`], see [`add_vote`][FakeTopgg::add_vote] for that.
+  pub fn add_voter(&self, voter: Value) {
+    self.state.lock().unwrap().voters.push(voter);
+  }
+
+  /// Marks a user as having voted, which affects [`Client::has_voted`].
+  #[inline(always)]
+  pub fn add_vote(&self, user_id: u64) {
+    self.state.lock().unwrap().votes.insert(user_id);
+  }
+
+  /// Sets whether the weekend multiplier is active.
+  #[inline(always)]
+  pub fn set_weekend(&self, is_weekend: bool) {
+    self.state.lock().unwrap().is_weekend = is_weekend;
+  }
+
+  /// Sets the Discord bot's statistics returned by [`Client::get_stats`].
+  #[inline(always)]
+  pub fn set_stats(&self, stats: Stats) {
+    self.state.lock().unwrap().stats = Some(stats);
+  }
+
+  /// Retrieves the Discord bot's statistics, either set from [`set_stats`][FakeTopgg::set_stats] or last posted by a client.
+  #[must_use]
+  #[inline(always)]
+  pub fn stats(&self) -> Option<Stats> {
+    self.state.lock().unwrap().stats.clone()
+  }
+
+  /// Injects a failure into the next response. Failures are queued, so calling this multiple times makes the next few responses fail in order.
+  ///
+  /// Injected failures take precedence over token validation.
+  #[inline(always)]
+  pub fn inject(&self, failure: Failure) {
+    self.state.lock().unwrap().failures.push_back(failure);
+  }
+
+  /// The amount of requests received by this fake server so far, including rejected ones.
+  #[must_use]
+  #[inline(always)]
+  pub fn requests(&self) -> usize {
+    self.state.lock().unwrap().requests
+  }
+}
+
+impl Drop for FakeTopgg {
+  #[inline(always)]
+  fn drop(&mut self) {
+    self.task.abort();
+  }
+}
+
+fn respond(status: StatusCode, body: &Value) -> Response<Full<Bytes>> {
+  Response::builder()
+    .status(status)
+    .header(header::CONTENT_TYPE, "application/json")
+    .body(Full::new(Bytes::from(body.to_string())))
+    .unwrap()
+}
+
+#[inline(always)]
+fn not_found() -> Response<Full<Bytes>> {
+  respond(StatusCode::NOT_FOUND, &json!({ "error": "Not Found" }))
+}
+
+// filters and sorts bots the same way GET /bots does, supporting `field: value` searches.
+fn search_bots(bots: &[Value], query: &HashMap<String, String>) -> Value {
+  let mut results: Vec<&Value> = bots.iter().collect();
+
+  if let Some(search) = query.get("search") {
+    let mut filters = Vec::new();
+    let mut rest = search.as_str();
+
+    while let Some((field, after)) = rest.split_once(": ") {
+      let (value, next) = match after.find(':') {
+        Some(colon) => {
+          let end = after[..colon].rfind(' ').unwrap_or(0);
+
+          (&after[..end], &after[end..])
+        }
+        None => (after, ""),
+      };
+
+      filters.push((field.trim(), value.trim().to_lowercase()));
+      rest = next;
+    }
+
+    results.retain(|bot| {
+      filters.iter().all(|(field, value)| match &bot[*field] {
+        Value::String(s) => s.to_lowercase().contains(value),
+        Value::Array(values) => values
+          .iter()
+          .any(|v| v.as_str().is_some_and(|s| s.to_lowercase() == *value)),
+        Value::Number(n) => value.parse::<f64>().ok() == n.as_f64(),
+        Value::Bool(b) => value.parse() == Ok(*b),
+        _ => false,
+      })
+    });
+  }
+
+  if let Some(sort) = query.get("sort") {
+    let (field, descending) = match sort.strip_prefix('-') {
+      Some(field) => (field, true),
+      None => (sort.as_str(), false),
+    };
+
+    results.sort_by(|a, b| {
+      let ordering = match (&a[field], &b[field]) {
+        (Value::Number(a), Value::Number(b)) => a.as_f64().partial_cmp(&b.as_f64()).unwrap(),
+        (a, b) => a.to_string().cmp(&b.to_string()),
+      };
+
+      if descending {
+        ordering.reverse()
+      } else {
+        ordering
+      }
+    });
+  }
+
+  let total = results.len();
+  let limit = query
+    .get("limit")
+    .and_then(|limit| limit.parse().ok())
+    .unwrap_or(50usize);
+  let offset = query
+    .get("offset")
+    .and_then(|offset| offset.parse().ok())
+    .unwrap_or(0usize);
+
+  let results: Vec<&Value> = results.into_iter().skip(offset).take(limit).collect();
+
+  json!({
+    "results": results,
+    "limit": limit,
+    "offset": offset,
+    "count": results.len(),
+    "total": total,
+  })
+}
+
+async fn handle(state: &Mutex<State>, request: Request<Incoming>) -> Response<Full<Bytes>> {
+  let (parts, body) = request.into_parts();
+  let body = match body.collect().await {
+    Ok(body) => body.to_bytes(),
+    Err(_) => return respond(StatusCode::BAD_REQUEST, &json!({})),
+  };
+
+  let url = Url::parse(&format!("http://localhost{}", parts.uri)).unwrap();
+  let query: HashMap<String, String> = url.query_pairs().into_owned().collect();
+  let segments: Vec<&str> = url.path().trim_matches('/').split('/').collect();
+
+  let mut state = state.lock().unwrap();
+
+  state.requests += 1;
+
+  if let Some(failure) = state.failures.pop_front() {
+    return match failure {
+      Failure::Status(status) => respond(
+        StatusCode::from_u16(status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR),
+        &json!({}),
+      ),
+
+      Failure::Ratelimit(retry_after) => respond(
+        StatusCode::TOO_MANY_REQUESTS,
+        &json!({ "retry-after": retry_after }),
+      ),
+    };
+  }
+
+  let authorized = parts
+    .headers
+    .get(header::AUTHORIZATION)
+    .is_some_and(|authorization| authorization.as_bytes() == state.token.as_bytes());
+
+  if !authorized {
+    return respond(
+      StatusCode::UNAUTHORIZED,
+      &json!({ "error": "Unauthorized" }),
+    );
+  }
+
+  match (&parts.method, segments.as_slice()) {
+    (&Method::GET, ["bots"]) => respond(StatusCode::OK, &search_bots(&state.bots, &query)),
+
+    (&Method::GET, ["bots", "stats"]) => respond(
+      StatusCode::OK,
+      &serde_json::to_value(state.stats.clone().unwrap_or_else(|| Stats::from(0)))
+        .unwrap_or_default(),
+    ),
+
+    (&Method::POST, ["bots", "stats"]) => match serde_json::from_slice(&body) {
+      Ok(stats) => {
+        state.stats = Some(stats);
+
+        respond(StatusCode::OK, &json!({}))
+      }
+
+      Err(_) => respond(StatusCode::BAD_REQUEST, &json!({ "error": "Bad Request" })),
+    },
+
+    (&Method::GET, ["bots", "votes"]) => {
+      let page = query
+        .get("page")
+        .and_then(|page| page.parse().ok())
+        .unwrap_or(1usize)
+        .max(1);
+
+      let voters: Vec<&Value> = state
+        .voters
+        .iter()
+        .rev()
+        .skip((page - 1) * VOTERS_PER_PAGE)
+        .take(VOTERS_PER_PAGE)
+        .collect();
+
+      respond(StatusCode::OK, &json!(voters))
+    }
+
+    (&Method::GET, ["bots", "check"]) => {
+      let voted = query
+        .get("userId")
+        .and_then(|user_id| user_id.parse().ok())
+        .is_some_and(|user_id| state.votes.contains(&user_id));
+
+      respond(StatusCode::OK, &json!({ "voted": voted as u8 }))
+    }
+
+    (&Method::GET, ["bots", id]) => match state.bots.iter().find(|bot| bot["id"] == *id) {
+      Some(bot) => respond(StatusCode::OK, bot),
+      None => not_found(),
+    },
+
+    (&Method::GET, ["users", id]) => match state.users.get(*id) {
+      Some(user) => respond(StatusCode::OK, user),
+      None => not_found(),
+    },
+
+    (&Method::GET, ["weekend"]) => {
+      respond(StatusCode::OK, &json!({ "is_weekend": state.is_weekend }))
+    }
+
+    _ => not_found(),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::FakeTopgg;
+  use crate::{Client, Error, Stats};
+  use serde_json::json;
+
+  #[tokio::test]
+  async fn serves_client() {
+    let fake = FakeTopgg::start().await.unwrap();
+    let client = fake.client();
+
+    fake.add_vote(661200758510977084);
+    fake.add_bot(json!({
+      "id": "264811613708746752",
+      "username": "Luca",
+      "discriminator": "1375",
+      "prefix": "- or @Luca",
+      "shortdesc": "Luca is a bot for managing and informing members of the server",
+      "owners": ["129908908096487424"],
+      "date": "2017-04-26T18:08:17.125Z",
+      "certifiedBot": false,
+      "points": 397,
+      "monthlyPoints": 19,
+    }));
+
+    assert!(client.has_voted(661200758510977084).await.unwrap());
+    assert!(!client.has_voted(264811613708746752).await.unwrap());
+    assert_eq!(
+      client.get_bot(264811613708746752).await.unwrap().username,
+      "Luca"
+    );
+
+    assert_eq!(client.get_stats().await.unwrap().server_count(), Some(0));
+
+    client.post_stats(Stats::from(12345)).await.unwrap();
+
+    assert_eq!(fake.stats().unwrap().server_count(), Some(12345));
+    assert_eq!(
+      client.get_stats().await.unwrap().server_count(),
+      Some(12345)
+    );
+  }
+
+  #[tokio::test]
+  async fn rejects_invalid_token() {
+    let fake = FakeTopgg::start().await.unwrap();
+    let client = Client::builder(String::from("another-token"))
+      .base_url(fake.url())
+      .ratelimiter(false)
+      .build()
+      .unwrap();
+
+    assert!(matches!(
+      client.is_weekend().await,
+      Err(Error::Unauthorized)
+    ));
+  }
+}
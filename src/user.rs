@@ -1,6 +1,7 @@
 use crate::{snowflake, util};
 use chrono::{DateTime, Utc};
 use serde::Deserialize;
+use std::collections::HashMap;
 
 /// A struct representing a user's social links.
 #[derive(Clone, Debug, Deserialize)]
@@ -114,6 +115,10 @@ util::debug_struct! {
 
       /// The username of this user.
       username: String,
+
+      /// Any other fields returned by [Top.gg](https://top.gg) for this voter that aren't covered by this struct.
+      #[serde(flatten)]
+      extra: HashMap<String, serde_json::Value>,
     }
 
     private {
@@ -5,6 +5,20 @@ use serde::{de::DeserializeOwned, Deserialize, Deserializer};
 
 const DISCORD_EPOCH: u64 = 1_420_070_400_000;
 
+pub(crate) const DEFAULT_BASE_URL: &str = "https://top.gg/api";
+
+macro_rules! api {
+  ($client:expr, $e:literal) => {
+    format!(concat!("{}", $e), $client.base_url)
+  };
+
+  ($client:expr, $e:literal, $($rest:tt)*) => {
+    format!(concat!("{}", $e), $client.base_url, $($rest)*)
+  };
+}
+
+pub(crate) use api;
+
 macro_rules! debug_struct {
   (
     $(#[$struct_attr:meta])*
@@ -112,18 +126,36 @@ pub(crate) fn get_creation_date(id: u64) -> DateTime<Utc> {
     .unwrap()
 }
 
+const MAX_BODY_LEN: usize = 1024;
+
 #[inline(always)]
 pub(crate) async fn parse_json<T>(response: Response) -> crate::Result<T>
 where
   T: DeserializeOwned,
 {
-  if let Ok(bytes) = response.bytes().await {
-    if let Ok(json) = serde_json::from_slice(&bytes) {
-      return Ok(json);
+  let bytes = response.bytes().await?;
+  let mut deserializer = serde_json::Deserializer::from_slice(&bytes);
+
+  serde_path_to_error::deserialize(&mut deserializer).map_err(|err| Error::Deserialize {
+    path: err.path().to_string(),
+    source: err.into_inner(),
+  })
+}
+
+pub(crate) fn truncate_body(body: &[u8]) -> String {
+  let mut body = String::from_utf8_lossy(body).into_owned();
+
+  if body.len() > MAX_BODY_LEN {
+    let mut end = MAX_BODY_LEN;
+
+    while !body.is_char_boundary(end) {
+      end -= 1;
     }
+
+    body.truncate(end);
   }
 
-  Err(Error::InternalServerError)
+  body
 }
 
 pub(crate) fn get_avatar(hash: &Option<String>, id: u64) -> String {
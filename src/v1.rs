@@ -0,0 +1,184 @@
+use crate::{
+  project::{Project, ProjectVote, ProjectVotes, VoteStatus},
+  util::api,
+  Error, InnerClient, Result, TrySnowflake,
+};
+use chrono::{DateTime, SecondsFormat, Utc};
+use futures_util::stream::{self, Stream, TryStreamExt};
+use reqwest::{Method, Url};
+
+/// A client view for [Top.gg](https://top.gg)'s v1 project-based API. (See [`Client::v1`][crate::Client::v1])
+///
+/// It shares the token, ratelimiter, retry policy and error handling of the [`Client`][crate::Client] it's created from.
+#[must_use]
+#[derive(Clone, Copy, Debug)]
+pub struct Client<'a> {
+  inner: &'a InnerClient,
+}
+
+impl<'a> Client<'a> {
+  #[inline(always)]
+  pub(crate) fn new(inner: &'a InnerClient) -> Self {
+    Self { inner }
+  }
+
+  /// Fetches the project that the client's [Top.gg](https://top.gg) token belongs to.
+  ///
+  /// # Errors
+  ///
+  /// Errors if any of the following conditions are met:
+  /// - An internal error from the client itself preventing it from sending a HTTP request to [Top.gg](https://top.gg) ([`InternalClientError`][crate::Error::InternalClientError])
+  /// - An unexpected HTTP response from the [Top.gg](https://top.gg) servers ([`Http`][crate::Error::Http])
+  /// - The response from the [Top.gg](https://top.gg) servers cannot be deserialized ([`Deserialize`][crate::Error::Deserialize])
+  /// - The request timed out ([`Timeout`][crate::Error::Timeout])
+  /// - The client uses an invalid [Top.gg API](https://docs.top.gg) token ([`Unauthorized`][crate::Error::Unauthorized])
+  /// - The client's [Top.gg API](https://docs.top.gg) token is not allowed to access the requested resource ([`Forbidden`][crate::Error::Forbidden])
+  /// - The client is being ratelimited from sending more HTTP requests ([`Ratelimit`][crate::Error::Ratelimit])
+  pub async fn get_project(&self) -> Result<Project> {
+    self
+      .inner
+      .send(Method::GET, api!(self.inner, "/v1/projects/@me"), None)
+      .await
+  }
+
+  /// Fetches the first page of your project's votes that were created since the specified date.
+  ///
+  /// Use the returned [`cursor`][ProjectVotes::cursor] with [`get_votes_after`][Client::get_votes_after] to fetch the next pages.
+  ///
+  /// # Errors
+  ///
+  /// Errors if any of the following conditions are met:
+  /// - An internal error from the client itself preventing it from sending a HTTP request to [Top.gg](https://top.gg) ([`InternalClientError`][crate::Error::InternalClientError])
+  /// - An unexpected HTTP response from the [Top.gg](https://top.gg) servers ([`Http`][crate::Error::Http])
+  /// - The response from the [Top.gg](https://top.gg) servers cannot be deserialized ([`Deserialize`][crate::Error::Deserialize])
+  /// - The request timed out ([`Timeout`][crate::Error::Timeout])
+  /// - The client uses an invalid [Top.gg API](https://docs.top.gg) token ([`Unauthorized`][crate::Error::Unauthorized])
+  /// - The client's [Top.gg API](https://docs.top.gg) token is not allowed to access the requested resource ([`Forbidden`][crate::Error::Forbidden])
+  /// - The client is being ratelimited from sending more HTTP requests ([`Ratelimit`][crate::Error::Ratelimit])
+  pub async fn get_votes(&self, since: DateTime<Utc>) -> Result<ProjectVotes> {
+    self
+      .get_votes_page(
+        "startDate",
+        &since.to_rfc3339_opts(SecondsFormat::Secs, true),
+      )
+      .await
+  }
+
+  /// Fetches the page of your project's votes that comes after the specified cursor. (See [`get_votes`][Client::get_votes])
+  ///
+  /// # Errors
+  ///
+  /// Errors if any of the following conditions are met:
+  /// - An internal error from the client itself preventing it from sending a HTTP request to [Top.gg](https://top.gg) ([`InternalClientError`][crate::Error::InternalClientError])
+  /// - An unexpected HTTP response from the [Top.gg](https://top.gg) servers ([`Http`][crate::Error::Http])
+  /// - The response from the [Top.gg](https://top.gg) servers cannot be deserialized ([`Deserialize`][crate::Error::Deserialize])
+  /// - The request timed out ([`Timeout`][crate::Error::Timeout])
+  /// - The client uses an invalid [Top.gg API](https://docs.top.gg) token ([`Unauthorized`][crate::Error::Unauthorized])
+  /// - The client's [Top.gg API](https://docs.top.gg) token is not allowed to access the requested resource ([`Forbidden`][crate::Error::Forbidden])
+  /// - The client is being ratelimited from sending more HTTP requests ([`Ratelimit`][crate::Error::Ratelimit])
+  #[inline(always)]
+  pub async fn get_votes_after(&self, cursor: &str) -> Result<ProjectVotes> {
+    self.get_votes_page("cursor", cursor).await
+  }
+
+  async fn get_votes_page(&self, key: &str, value: &str) -> Result<ProjectVotes> {
+    let mut url = Url::parse(&api!(self.inner, "/v1/projects/@me/votes")).unwrap();
+
+    url.query_pairs_mut().append_pair(key, value);
+
+    self.inner.send(Method::GET, url, None).await
+  }
+
+  /// Creates a [`Stream`] of every one of your project's votes that were created since the specified date, following the cursor of each page until there are no more votes.
+  ///
+  /// The stream ends right after yielding an error.
+  ///
+  /// # Examples
+  ///
+  /// Basic usage:
+  ///
+  /// ```rust,no_run
+  /// use chrono::{DateTime, Duration, Utc};
+  /// use std::time::SystemTime;
+  /// use futures_util::StreamExt;
+  /// use topgg::Client;
+  ///
+  /// # async fn run(client: Client) -> topgg::Result<()> {
+  /// let v1 = client.v1();
+  /// let mut votes = Box::pin(v1.votes(DateTime::<Utc>::from(SystemTime::now()) - Duration::days(7)));
+  ///
+  /// while let Some(vote) = votes.next().await {
+  ///   println!("{:?}", vote?);
+  /// }
+  /// # Ok(())
+  /// # }
+  /// ```
+  pub fn votes(&self, since: DateTime<Utc>) -> impl Stream<Item = Result<ProjectVote>> + 'a {
+    let client = *self;
+
+    stream::unfold(
+      Some(None),
+      move |cursor: Option<Option<String>>| async move {
+        let page = match cursor? {
+          Some(cursor) => client.get_votes_after(&cursor).await,
+          None => client.get_votes(since).await,
+        };
+
+        match page {
+          Ok(page) => {
+            let next = match page.cursor {
+              Some(cursor) if !page.data.is_empty() => Some(Some(cursor)),
+              _ => None,
+            };
+
+            Some((Ok(page.data), next))
+          }
+
+          Err(err) => Some((Err(err), None)),
+        }
+      },
+    )
+    .map_ok(|votes| stream::iter(votes.into_iter().map(Ok)))
+    .try_flatten()
+  }
+
+  /// Fetches the specified Discord user's latest vote on your project, or [`None`] if they have never voted.
+  ///
+  /// Use [`VoteStatus::is_active`] to check if the vote hasn't expired yet.
+  ///
+  /// # Errors
+  ///
+  /// Errors if any of the following conditions are met:
+  /// - The ID argument is not a valid Discord snowflake/ID, e.g. a string that is not numeric ([`InvalidId`][crate::Error::InvalidId])
+  /// - An internal error from the client itself preventing it from sending a HTTP request to [Top.gg](https://top.gg) ([`InternalClientError`][crate::Error::InternalClientError])
+  /// - An unexpected HTTP response from the [Top.gg](https://top.gg) servers ([`Http`][crate::Error::Http])
+  /// - The response from the [Top.gg](https://top.gg) servers cannot be deserialized ([`Deserialize`][crate::Error::Deserialize])
+  /// - The request timed out ([`Timeout`][crate::Error::Timeout])
+  /// - The client uses an invalid [Top.gg API](https://docs.top.gg) token ([`Unauthorized`][crate::Error::Unauthorized])
+  /// - The client's [Top.gg API](https://docs.top.gg) token is not allowed to access the requested resource ([`Forbidden`][crate::Error::Forbidden])
+  /// - The client is being ratelimited from sending more HTTP requests ([`Ratelimit`][crate::Error::Ratelimit])
+  pub async fn get_vote<I>(&self, user_id: I) -> Result<Option<VoteStatus>>
+  where
+    I: TrySnowflake,
+  {
+    let user_id = user_id.try_as_snowflake().ok_or(Error::InvalidId)?;
+
+    match self
+      .inner
+      .send(
+        Method::GET,
+        api!(
+          self.inner,
+          "/v1/projects/@me/votes/{}?source=discord",
+          user_id
+        ),
+        None,
+      )
+      .await
+    {
+      Ok(status) => Ok(Some(status)),
+      Err(Error::NotFound) => Ok(None),
+      Err(err) => Err(err),
+    }
+  }
+}
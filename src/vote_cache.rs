@@ -0,0 +1,164 @@
+use crate::{Client, Error, Result, TrySnowflake, Vote};
+use core::time::Duration;
+use std::{collections::HashMap, sync::Mutex, time::Instant};
+
+/// How long a vote lasts on [Top.gg](https://top.gg) before the user is allowed to vote again.
+const VOTE_DURATION: Duration = Duration::from_secs(12 * 60 * 60);
+
+#[derive(Clone, Copy)]
+struct Entry {
+  voted: bool,
+  expires_at: Instant,
+}
+
+/// A struct that answers whether a user has voted your Discord bot in the last 12 hours, primarily from the [`Vote`]s received through your webhook.
+///
+/// [`has_voted`][VoteCache::has_voted] only falls back to [`Client::has_voted`] on a cache miss, and caches its result afterwards:
+/// - A user who voted through your webhook is cached as voted for 12 hours after receiving the [`Vote`].
+/// - A user who has voted according to [`Client::has_voted`] is cached as voted for 5 minutes by default, as [Top.gg](https://top.gg) doesn't tell when they voted.
+/// - A user who hasn't voted according to [`Client::has_voted`] is cached as such for 1 minute by default, or until a [`Vote`] from them is received.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust,no_run
+/// use topgg::{Client, Vote, VoteCache};
+///
+/// # async fn run(client: Client, vote: Vote) -> topgg::Result<()> {
+/// let cache = VoteCache::new(client);
+///
+/// // in your webhook handler...
+/// cache.record(&vote);
+///
+/// // in your vote-locked commands...
+/// if cache.has_voted(661200758510977084).await? {
+///   println!("checks out");
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[cfg_attr(docsrs, doc(cfg(all(feature = "api", feature = "webhook"))))]
+pub struct VoteCache {
+  client: Client,
+  voted_ttl: Duration,
+  not_voted_ttl: Duration,
+  max_entries: usize,
+  entries: Mutex<HashMap<u64, Entry>>,
+}
+
+impl VoteCache {
+  /// Creates a brand new and empty [`VoteCache`] that falls back to the specified [`Client`] on cache misses.
+  #[inline(always)]
+  pub fn new(client: Client) -> Self {
+    Self {
+      client,
+      voted_ttl: Duration::from_secs(300),
+      not_voted_ttl: Duration::from_secs(60),
+      max_entries: 100_000,
+      entries: Mutex::new(HashMap::new()),
+    }
+  }
+
+  /// Sets how long a positive [`Client::has_voted`] result stays cached. Defaults to 5 minutes.
+  #[must_use]
+  #[inline(always)]
+  pub fn voted_ttl(mut self, ttl: Duration) -> Self {
+    self.voted_ttl = ttl;
+    self
+  }
+
+  /// Sets how long a negative [`Client::has_voted`] result stays cached. Defaults to 1 minute.
+  #[must_use]
+  #[inline(always)]
+  pub fn not_voted_ttl(mut self, ttl: Duration) -> Self {
+    self.not_voted_ttl = ttl;
+    self
+  }
+
+  /// Sets the amount of cached users before expired entries are removed. Defaults to 100000.
+  #[must_use]
+  #[inline(always)]
+  pub fn max_entries(mut self, max_entries: usize) -> Self {
+    self.max_entries = max_entries;
+    self
+  }
+
+  /// Retrieves the [`Client`] used on cache misses.
+  #[inline(always)]
+  pub fn client(&self) -> &Client {
+    &self.client
+  }
+
+  fn insert(&self, user_id: u64, voted: bool, ttl: Duration) {
+    let now = Instant::now();
+    let mut entries = self.entries.lock().unwrap();
+
+    if entries.len() >= self.max_entries && !entries.contains_key(&user_id) {
+      entries.retain(|_, entry| entry.expires_at > now);
+    }
+
+    entries.insert(
+      user_id,
+      Entry {
+        voted,
+        expires_at: now + ttl,
+      },
+    );
+  }
+
+  /// Records a [`Vote`] received through your webhook. Test votes and votes for Discord servers are ignored.
+  pub fn record(&self, vote: &Vote) {
+    if !vote.is_test && vote.target.is_bot() {
+      self.insert(vote.voter_id, true, VOTE_DURATION);
+    }
+  }
+
+  /// Removes a user from this cache, so that the next [`has_voted`][VoteCache::has_voted] call falls back to [`Client::has_voted`].
+  ///
+  /// This does nothing if the ID argument is not a valid Discord snowflake/ID.
+  pub fn invalidate<I>(&self, user_id: I)
+  where
+    I: TrySnowflake,
+  {
+    if let Some(user_id) = user_id.try_as_snowflake() {
+      self.entries.lock().unwrap().remove(&user_id);
+    }
+  }
+
+  /// Checks if the specified user has voted your Discord bot in the last 12 hours, falling back to [`Client::has_voted`] on a cache miss.
+  ///
+  /// # Errors
+  ///
+  /// Errors if the user is not cached and [`Client::has_voted`] errors.
+  pub async fn has_voted<I>(&self, user_id: I) -> Result<bool>
+  where
+    I: TrySnowflake,
+  {
+    let user_id = user_id.try_as_snowflake().ok_or(Error::InvalidId)?;
+
+    {
+      let entries = self.entries.lock().unwrap();
+
+      if let Some(entry) = entries.get(&user_id) {
+        if entry.expires_at > Instant::now() {
+          return Ok(entry.voted);
+        }
+      }
+    }
+
+    let voted = self.client.has_voted(user_id).await?;
+
+    self.insert(
+      user_id,
+      voted,
+      if voted {
+        self.voted_ttl
+      } else {
+        self.not_voted_ttl
+      },
+    );
+
+    Ok(voted)
+  }
+}
@@ -1,53 +1,180 @@
-use crate::{IncomingVote, Vote};
+use super::verify::Incoming;
+use crate::{
+  FallibleVoteHandler, IncomingEvent, IncomingVote, Passwords, WebhookEventHandler, WebhookSecret,
+};
 use actix_web::{
   dev::Payload,
-  error::{Error, ErrorUnauthorized},
-  web::Json,
-  FromRequest, HttpRequest,
+  error::Error,
+  http::StatusCode,
+  web::{self, Bytes, Data},
+  FromRequest, HttpRequest, HttpResponse, Resource,
 };
 use core::{
   future::Future,
+  marker::PhantomData,
   pin::Pin,
   task::{ready, Context, Poll},
 };
+use std::sync::Arc;
 
 #[doc(hidden)]
-pub struct IncomingVoteFut {
+pub struct IncomingFut<T> {
   req: HttpRequest,
-  json_fut: <Json<Vote> as FromRequest>::Future,
+  bytes_fut: <Bytes as FromRequest>::Future,
+  _incoming: PhantomData<fn() -> T>,
 }
 
-impl Future for IncomingVoteFut {
-  type Output = Result<IncomingVote, Error>;
+impl<T> IncomingFut<T> {
+  #[inline(always)]
+  fn new(req: &HttpRequest, payload: &mut Payload) -> Self {
+    Self {
+      req: req.clone(),
+      bytes_fut: Bytes::from_request(req, payload),
+      _incoming: PhantomData,
+    }
+  }
+}
+
+impl<T> Future for IncomingFut<T>
+where
+  T: Incoming,
+{
+  type Output = Result<T, Error>;
 
   fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
-    if let Ok(json) = ready!(Pin::new(&mut self.json_fut).poll(cx)) {
-      let headers = self.req.headers();
-
-      if let Some(authorization) = headers.get("Authorization") {
-        if let Ok(authorization) = authorization.to_str() {
-          return Poll::Ready(Ok(IncomingVote {
-            authorization: authorization.to_owned(),
-            vote: json.into_inner(),
-          }));
-        }
-      }
-    }
+    let body = ready!(Pin::new(&mut self.bytes_fut).poll(cx))?;
+    let credentials = self
+      .req
+      .headers()
+      .get(T::HEADER)
+      .and_then(|credentials| credentials.to_str().ok())
+      .map(String::from);
 
-    Poll::Ready(Err(ErrorUnauthorized("401")))
+    Poll::Ready(Ok(T::new(credentials, body.to_vec())))
   }
 }
 
 #[cfg_attr(docsrs, doc(cfg(feature = "actix-web")))]
 impl FromRequest for IncomingVote {
   type Error = Error;
-  type Future = IncomingVoteFut;
+  type Future = IncomingFut<Self>;
 
   #[inline(always)]
   fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
-    IncomingVoteFut {
-      req: req.clone(),
-      json_fut: Json::from_request(req, payload),
+    IncomingFut::new(req, payload)
+  }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "actix-web")))]
+impl FromRequest for IncomingEvent {
+  type Error = Error;
+  type Future = IncomingFut<Self>;
+
+  #[inline(always)]
+  fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+    IncomingFut::new(req, payload)
+  }
+}
+
+struct WebhookState<T> {
+  state: Arc<T>,
+  passwords: Passwords,
+}
+
+async fn handler<T>(webhook: Data<WebhookState<T>>, vote: IncomingVote) -> HttpResponse
+where
+  T: FallibleVoteHandler,
+{
+  let result = match vote.verify(&webhook.passwords) {
+    Ok(vote) => crate::handle_vote(&webhook.state, vote).await,
+    Err(err) => Err(err),
+  };
+
+  match result {
+    Ok(()) => HttpResponse::Ok().finish(),
+    Err(err) => HttpResponse::new(StatusCode::from_u16(err.status_code()).unwrap()),
+  }
+}
+
+struct EventWebhookState<T> {
+  state: Arc<T>,
+  secret: WebhookSecret,
+}
+
+async fn event_handler<T>(webhook: Data<EventWebhookState<T>>, event: IncomingEvent) -> HttpResponse
+where
+  T: WebhookEventHandler,
+{
+  match event.verify(&webhook.secret) {
+    Ok(event) => {
+      webhook.state.handle(event).await;
+
+      HttpResponse::Ok().finish()
     }
+
+    Err(err) => HttpResponse::new(StatusCode::from_u16(err.status_code()).unwrap()),
   }
 }
+
+/// Creates a new `actix-web` [`Resource`] at the specified path for adding an on-vote event handler to your application logic. (See [`Passwords`])
+///
+/// The returned [`Resource`] can be configured further, e.g. with guards or middleware, before being registered to an `App` or a `Scope`.
+///
+/// `state` can also be a [`FallibleVoteHandler`].
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust,no_run
+/// use actix_web::App;
+/// use std::sync::Arc;
+/// use topgg::{Vote, VoteHandler};
+///
+/// struct MyVoteHandler {}
+///
+/// #[async_trait::async_trait]
+/// impl VoteHandler for MyVoteHandler {
+///   async fn voted(&self, vote: Vote) {
+///     println!("{:?}", vote);
+///   }
+/// }
+///
+/// # fn run() {
+/// let state = Arc::new(MyVoteHandler {});
+///
+/// let app = App::new().service(topgg::actix_web::webhook(
+///   "/webhook",
+///   "my-webhook-password",
+///   Arc::clone(&state),
+/// ));
+/// # }
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "actix-web")))]
+pub fn webhook<P, T>(path: &str, passwords: P, state: Arc<T>) -> Resource
+where
+  P: Into<Passwords>,
+  T: FallibleVoteHandler,
+{
+  web::resource(path)
+    .app_data(Data::new(WebhookState {
+      state,
+      passwords: passwords.into(),
+    }))
+    .route(web::post().to(handler::<T>))
+}
+
+/// Creates a new `actix-web` [`Resource`] at the specified path for adding an on-event handler for signed [Top.gg](https://top.gg) webhook deliveries to your application logic. (See [`webhook`])
+#[cfg_attr(docsrs, doc(cfg(feature = "actix-web")))]
+pub fn webhook_v1<S, T>(path: &str, secret: S, state: Arc<T>) -> Resource
+where
+  S: Into<WebhookSecret>,
+  T: WebhookEventHandler,
+{
+  web::resource(path)
+    .app_data(Data::new(EventWebhookState {
+      state,
+      secret: secret.into(),
+    }))
+    .route(web::post().to(event_handler::<T>))
+}
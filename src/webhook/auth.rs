@@ -0,0 +1,139 @@
+use sha2::{Digest, Sha256};
+use subtle::ConstantTimeEq;
+
+/// Compares two byte strings in constant time.
+///
+/// Both sides are hashed first so that the comparison doesn't leak the length of the expected value either.
+pub(crate) fn constant_time_eq(expected: &[u8], actual: &[u8]) -> bool {
+  Sha256::digest(expected)
+    .ct_eq(&Sha256::digest(actual))
+    .into()
+}
+
+/// One or more accepted passwords for authenticating incoming [Top.gg](https://top.gg) webhook requests through their `Authorization` header.
+///
+/// Every webhook wrapper in this SDK checks requests against these in constant time. Accepting several passwords at once allows rotating a webhook password without rejecting votes during the switch.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust,no_run
+/// use topgg::Passwords;
+///
+/// let passwords = Passwords::new("my-new-password").and("my-old-password");
+///
+/// assert!(passwords.verify("my-old-password"));
+/// assert!(!passwords.verify("an-attacker's-guess"));
+/// ```
+#[must_use]
+#[derive(Clone)]
+pub struct Passwords(Vec<String>);
+
+impl Passwords {
+  /// Creates a brand new [`Passwords`] that accepts a single password.
+  #[inline(always)]
+  pub fn new<S>(password: S) -> Self
+  where
+    S: Into<String>,
+  {
+    Self(vec![password.into()])
+  }
+
+  /// Accepts another password.
+  #[inline(always)]
+  pub fn and<S>(mut self, password: S) -> Self
+  where
+    S: Into<String>,
+  {
+    self.0.push(password.into());
+    self
+  }
+
+  /// Checks if the specified `Authorization` header value matches any of the accepted passwords, in constant time.
+  #[must_use]
+  pub fn verify(&self, authorization: &str) -> bool {
+    self.0.iter().fold(false, |matched, password| {
+      matched | constant_time_eq(password.as_bytes(), authorization.as_bytes())
+    })
+  }
+}
+
+impl From<String> for Passwords {
+  #[inline(always)]
+  fn from(password: String) -> Self {
+    Self::new(password)
+  }
+}
+
+impl From<&str> for Passwords {
+  #[inline(always)]
+  fn from(password: &str) -> Self {
+    Self::new(password)
+  }
+}
+
+impl From<&String> for Passwords {
+  #[inline(always)]
+  fn from(password: &String) -> Self {
+    Self::new(password.as_str())
+  }
+}
+
+impl From<Vec<String>> for Passwords {
+  #[inline(always)]
+  fn from(passwords: Vec<String>) -> Self {
+    Self(passwords)
+  }
+}
+
+impl From<&Passwords> for Passwords {
+  #[inline(always)]
+  fn from(passwords: &Passwords) -> Self {
+    passwords.clone()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn matches_any_password() {
+    let passwords = Passwords::new("my-new-password").and("my-old-password");
+
+    assert!(passwords.verify("my-new-password"));
+    assert!(passwords.verify("my-old-password"));
+    assert!(!passwords.verify("my-other-password"));
+  }
+
+  #[test]
+  fn rejects_different_lengths() {
+    let passwords = Passwords::new("my-password");
+
+    assert!(!passwords.verify("my-passwor"));
+    assert!(!passwords.verify("my-password "));
+    assert!(!passwords.verify("my-password-and-more"));
+  }
+
+  #[test]
+  fn rejects_empty_header() {
+    assert!(!Passwords::new("my-password").verify(""));
+    assert!(!Passwords::from(Vec::new()).verify(""));
+  }
+
+  #[test]
+  fn converts_from_strings() {
+    let password = String::from("my-password");
+
+    for passwords in [
+      Passwords::from("my-password"),
+      Passwords::from(&password),
+      Passwords::from(password.clone()),
+      Passwords::from(vec![password.clone()]),
+      Passwords::from(&Passwords::new("my-password")),
+    ] {
+      assert!(passwords.verify("my-password"));
+    }
+  }
+}
@@ -1,6 +1,11 @@
-use crate::VoteHandler;
+use super::verify::Incoming;
+use crate::{
+  FallibleVoteHandler, IncomingEvent, IncomingVote, Passwords, WebhookEventHandler, WebhookSecret,
+};
 use axum::{
-  extract::State,
+  async_trait,
+  body::Bytes,
+  extract::{rejection::BytesRejection, FromRequest, Request, State},
   http::{HeaderMap, StatusCode},
   response::{IntoResponse, Response},
   routing::post,
@@ -8,9 +13,53 @@ use axum::{
 };
 use std::sync::Arc;
 
+async fn from_request<T, S>(request: Request, state: &S) -> Result<T, BytesRejection>
+where
+  T: Incoming,
+  S: Send + Sync,
+{
+  let credentials = request
+    .headers()
+    .get(T::HEADER)
+    .and_then(|credentials| credentials.to_str().ok())
+    .map(String::from);
+
+  let body = Bytes::from_request(request, state).await?;
+
+  Ok(T::new(credentials, body.to_vec()))
+}
+
+#[async_trait]
+#[cfg_attr(docsrs, doc(cfg(feature = "axum")))]
+impl<S> FromRequest<S> for IncomingVote
+where
+  S: Send + Sync,
+{
+  type Rejection = BytesRejection;
+
+  #[inline(always)]
+  async fn from_request(request: Request, state: &S) -> Result<Self, Self::Rejection> {
+    from_request(request, state).await
+  }
+}
+
+#[async_trait]
+#[cfg_attr(docsrs, doc(cfg(feature = "axum")))]
+impl<S> FromRequest<S> for IncomingEvent
+where
+  S: Send + Sync,
+{
+  type Rejection = BytesRejection;
+
+  #[inline(always)]
+  async fn from_request(request: Request, state: &S) -> Result<Self, Self::Rejection> {
+    from_request(request, state).await
+  }
+}
+
 struct WebhookState<T> {
   state: Arc<T>,
-  password: Arc<String>,
+  passwords: Arc<Passwords>,
 }
 
 impl<T> Clone for WebhookState<T> {
@@ -18,7 +67,7 @@ impl<T> Clone for WebhookState<T> {
   fn clone(&self) -> Self {
     Self {
       state: Arc::clone(&self.state),
-      password: Arc::clone(&self.password),
+      passwords: Arc::clone(&self.passwords),
     }
   }
 }
@@ -26,27 +75,59 @@ impl<T> Clone for WebhookState<T> {
 async fn handler<T>(
   headers: HeaderMap,
   State(webhook): State<WebhookState<T>>,
-  body: String,
+  body: Bytes,
 ) -> Response
 where
-  T: VoteHandler,
+  T: FallibleVoteHandler,
 {
-  if let Some(authorization) = headers.get("Authorization") {
-    if let Ok(authorization) = authorization.to_str() {
-      if authorization == *(webhook.password) {
-        if let Ok(vote) = serde_json::from_str(&body) {
-          webhook.state.voted(vote).await;
-
-          return (StatusCode::OK, ()).into_response();
-        }
-      }
+  let result = match crate::verify_and_parse(&webhook.passwords, &headers, &body) {
+    Ok(vote) => crate::handle_vote(&webhook.state, vote).await,
+    Err(err) => Err(err),
+  };
+
+  match result {
+    Ok(()) => (StatusCode::OK, ()).into_response(),
+    Err(err) => (StatusCode::from_u16(err.status_code()).unwrap(), ()).into_response(),
+  }
+}
+
+struct EventWebhookState<T> {
+  state: Arc<T>,
+  secret: Arc<WebhookSecret>,
+}
+
+impl<T> Clone for EventWebhookState<T> {
+  #[inline(always)]
+  fn clone(&self) -> Self {
+    Self {
+      state: Arc::clone(&self.state),
+      secret: Arc::clone(&self.secret),
     }
   }
+}
 
-  (StatusCode::UNAUTHORIZED, ()).into_response()
+async fn event_handler<T>(
+  headers: HeaderMap,
+  State(webhook): State<EventWebhookState<T>>,
+  body: Bytes,
+) -> Response
+where
+  T: WebhookEventHandler,
+{
+  match crate::verify_and_parse_event(&webhook.secret, &headers, &body) {
+    Ok(event) => {
+      webhook.state.handle(event).await;
+
+      (StatusCode::OK, ()).into_response()
+    }
+
+    Err(err) => (StatusCode::from_u16(err.status_code()).unwrap(), ()).into_response(),
+  }
 }
 
-/// Creates a new [`axum`] [`Router`] for adding an on-vote event handler to your application logic.
+/// Creates a new [`axum`] [`Router`] for adding an on-vote event handler to your application logic. (See [`Passwords`])
+///
+/// `state` can also be a [`FallibleVoteHandler`].
 ///
 /// # Examples
 ///
@@ -89,14 +170,62 @@ where
 /// ```
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(feature = "axum")))]
-pub fn webhook<T>(password: String, state: Arc<T>) -> Router
+pub fn webhook<P, T>(passwords: P, state: Arc<T>) -> Router
 where
-  T: VoteHandler,
+  P: Into<Passwords>,
+  T: FallibleVoteHandler,
 {
   Router::new()
     .route("/", post(handler::<T>))
     .with_state(WebhookState {
       state,
-      password: Arc::new(password),
+      passwords: Arc::new(passwords.into()),
+    })
+}
+
+/// Creates a new [`axum`] [`Router`] for adding an on-event handler for signed [Top.gg](https://top.gg) webhook deliveries to your application logic.
+///
+/// Deliveries with an invalid signature or a timestamp outside of the replay window are rejected with a 401 (Unauthorized) HTTP status code.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust,no_run
+/// use axum::{routing::get, Router};
+/// use std::sync::Arc;
+/// use topgg::{WebhookEvent, WebhookEventHandler};
+///
+/// struct MyEventHandler {}
+///
+/// #[axum::async_trait]
+/// impl WebhookEventHandler for MyEventHandler {
+///   async fn handle(&self, event: WebhookEvent) {
+///     if let WebhookEvent::VoteCreate(vote) = event {
+///       println!("{} voted!", vote.user.name);
+///     }
+///   }
+/// }
+///
+/// # fn run() {
+/// let state = Arc::new(MyEventHandler {});
+///
+/// let app: Router = Router::new()
+///   .route("/", get(|| async { "Hello, World!" }))
+///   .nest("/webhook", topgg::axum::webhook_v1("my-webhook-secret", state));
+/// # }
+/// ```
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(feature = "axum")))]
+pub fn webhook_v1<S, T>(secret: S, state: Arc<T>) -> Router
+where
+  S: Into<WebhookSecret>,
+  T: WebhookEventHandler,
+{
+  Router::new()
+    .route("/", post(event_handler::<T>))
+    .with_state(EventWebhookState {
+      state,
+      secret: Arc::new(secret.into()),
     })
 }
@@ -0,0 +1,675 @@
+use super::log_file::{self, LogFile};
+use crate::{FallibleVoteHandler, Vote, WebhookEvent, WebhookEventHandler};
+use core::{fmt, time::Duration};
+use std::{
+  collections::{BTreeMap, HashMap, HashSet},
+  error, io,
+  path::Path,
+  sync::{Arc, Mutex},
+  time::{SystemTime, UNIX_EPOCH},
+};
+
+const DEFAULT_CAPACITY: usize = 10000;
+const DEFAULT_WINDOW: Duration = Duration::from_secs(3600);
+
+/// An async trait for storing the keys of webhook deliveries that have already been handled. (See [`Dedup`])
+///
+/// It's described as follows (without [`async_trait`]'s macro expansion):
+/// ```rust,no_run
+/// # use std::io;
+/// #[async_trait::async_trait]
+/// pub trait DedupStore: Send + Sync + 'static {
+///   async fn contains(&self, key: &str) -> io::Result<bool>;
+///   async fn insert(&self, key: &str) -> io::Result<bool>;
+///   async fn remove(&self, key: &str) -> io::Result<()>;
+/// }
+/// ```
+#[async_trait::async_trait]
+pub trait DedupStore: Send + Sync + 'static {
+  /// Checks if the specified key is stored.
+  async fn contains(&self, key: &str) -> io::Result<bool>;
+
+  /// Stores the specified key. Returns `true` if it wasn't stored before, otherwise `false`.
+  async fn insert(&self, key: &str) -> io::Result<bool>;
+
+  /// Forgets the specified key.
+  async fn remove(&self, key: &str) -> io::Result<()>;
+}
+
+/// A bounded set of keys that forgets its least recently used keys first.
+#[derive(Debug)]
+struct Lru {
+  keys: HashMap<String, u64>,
+  order: BTreeMap<u64, String>,
+  next_use: u64,
+  capacity: usize,
+}
+
+impl Lru {
+  #[inline(always)]
+  fn new(capacity: usize) -> Self {
+    Self {
+      keys: HashMap::new(),
+      order: BTreeMap::new(),
+      next_use: 0,
+      capacity: capacity.max(1),
+    }
+  }
+
+  /// Marks a key as the most recently used one. Returns `false` if it isn't stored.
+  fn touch(&mut self, key: &str) -> bool {
+    let Some(last_use) = self.keys.get_mut(key) else {
+      return false;
+    };
+
+    if let Some(key) = self.order.remove(last_use) {
+      *last_use = self.next_use;
+      self.order.insert(self.next_use, key);
+      self.next_use += 1;
+    }
+
+    true
+  }
+
+  #[inline(always)]
+  fn contains(&mut self, key: &str) -> bool {
+    self.touch(key)
+  }
+
+  fn insert(&mut self, key: &str) -> bool {
+    if self.touch(key) {
+      return false;
+    }
+
+    self.keys.insert(key.to_owned(), self.next_use);
+    self.order.insert(self.next_use, key.to_owned());
+    self.next_use += 1;
+
+    while self.keys.len() > self.capacity {
+      if let Some((_, oldest)) = self.order.pop_first() {
+        self.keys.remove(&oldest);
+      }
+    }
+
+    true
+  }
+
+  /// Forgets a key. Returns `false` if it wasn't stored.
+  fn remove(&mut self, key: &str) -> bool {
+    let Some(last_use) = self.keys.remove(key) else {
+      return false;
+    };
+
+    self.order.remove(&last_use);
+    true
+  }
+
+  /// Iterates over the stored keys, from the least to the most recently used one.
+  #[inline(always)]
+  fn iter(&self) -> impl Iterator<Item = &String> {
+    self.order.values()
+  }
+}
+
+/// An in-memory [`DedupStore`] that remembers a bounded amount of keys, forgetting the least recently used ones first.
+#[derive(Debug)]
+pub struct MemoryDedupStore(Mutex<Lru>);
+
+impl MemoryDedupStore {
+  /// Creates a brand new [`MemoryDedupStore`] that remembers at most the specified amount of keys.
+  #[must_use]
+  #[inline(always)]
+  pub fn new(capacity: usize) -> Self {
+    Self(Mutex::new(Lru::new(capacity)))
+  }
+}
+
+impl Default for MemoryDedupStore {
+  #[inline(always)]
+  fn default() -> Self {
+    Self::new(DEFAULT_CAPACITY)
+  }
+}
+
+#[async_trait::async_trait]
+impl DedupStore for MemoryDedupStore {
+  #[inline(always)]
+  async fn contains(&self, key: &str) -> io::Result<bool> {
+    Ok(self.0.lock().unwrap().contains(key))
+  }
+
+  #[inline(always)]
+  async fn insert(&self, key: &str) -> io::Result<bool> {
+    Ok(self.0.lock().unwrap().insert(key))
+  }
+
+  #[inline(always)]
+  async fn remove(&self, key: &str) -> io::Result<()> {
+    self.0.lock().unwrap().remove(key);
+
+    Ok(())
+  }
+}
+
+/// Appends a line to a [`FileDedupStore`]'s file, and rewrites it with only the keys that are still remembered once it grows too large.
+fn append(log: &mut LogFile, keys: &Mutex<Lru>, line: &str) -> io::Result<()> {
+  log.append(line)?;
+
+  let lines = {
+    let keys = keys.lock().unwrap();
+
+    (log.lines() > keys.capacity * 2)
+      .then(|| keys.iter().map(|key| format!("+{key}")).collect::<Vec<_>>())
+  };
+
+  match lines {
+    Some(lines) => log.compact(lines),
+    None => Ok(()),
+  }
+}
+
+/// A [`DedupStore`] backed by an append-only file, so that handled deliveries are remembered across restarts.
+///
+/// Like [`MemoryDedupStore`], it remembers a bounded amount of keys, and the file is compacted once it grows past twice that amount. Only additions and removals are written to the file, so after a restart, keys are forgotten in the order they were last used as of the last compaction. Keys must not contain newlines.
+///
+/// Writes to the file run on [`tokio`]'s blocking thread pool, and lookups never wait for them.
+#[derive(Debug)]
+pub struct FileDedupStore {
+  keys: Arc<Mutex<Lru>>,
+  log: Arc<Mutex<LogFile>>,
+}
+
+impl FileDedupStore {
+  /// Opens or creates a [`FileDedupStore`] at the specified path that remembers at most 10000 keys.
+  ///
+  /// # Errors
+  ///
+  /// Errors if the file cannot be read or created.
+  #[inline(always)]
+  pub fn open<P>(path: P) -> io::Result<Self>
+  where
+    P: AsRef<Path>,
+  {
+    Self::with_capacity(path, DEFAULT_CAPACITY)
+  }
+
+  /// Opens or creates a [`FileDedupStore`] at the specified path that remembers at most the specified amount of keys.
+  ///
+  /// # Errors
+  ///
+  /// Errors if the file cannot be read or created.
+  pub fn with_capacity<P>(path: P, capacity: usize) -> io::Result<Self>
+  where
+    P: AsRef<Path>,
+  {
+    let mut keys = Lru::new(capacity);
+
+    let log = LogFile::open(path, |line| {
+      if let Some(key) = line.strip_prefix('+') {
+        keys.insert(key);
+      } else if let Some(key) = line.strip_prefix('-') {
+        keys.remove(key);
+      }
+    })?;
+
+    Ok(Self {
+      keys: Arc::new(Mutex::new(keys)),
+      log: Arc::new(Mutex::new(log)),
+    })
+  }
+}
+
+#[async_trait::async_trait]
+impl DedupStore for FileDedupStore {
+  #[inline(always)]
+  async fn contains(&self, key: &str) -> io::Result<bool> {
+    Ok(self.keys.lock().unwrap().contains(key))
+  }
+
+  // the keys are changed while the file is locked, so that the file's lines are in the same order as the changes.
+  async fn insert(&self, key: &str) -> io::Result<bool> {
+    let keys = Arc::clone(&self.keys);
+    let key = key.to_owned();
+
+    log_file::run_blocking(&self.log, move |log| {
+      if !keys.lock().unwrap().insert(&key) {
+        return Ok(false);
+      }
+
+      append(log, &keys, &format!("+{key}"))?;
+
+      Ok(true)
+    })
+    .await
+  }
+
+  async fn remove(&self, key: &str) -> io::Result<()> {
+    let keys = Arc::clone(&self.keys);
+    let key = key.to_owned();
+
+    log_file::run_blocking(&self.log, move |log| {
+      let removed = keys.lock().unwrap().remove(&key);
+
+      if removed {
+        append(log, &keys, &format!("-{key}"))?;
+      }
+
+      Ok(())
+    })
+    .await
+  }
+}
+
+/// A wrapper around a [`VoteHandler`][crate::VoteHandler], a [`FallibleVoteHandler`] or a [`WebhookEventHandler`] that skips deliveries that have already been handled, since [Top.gg](https://top.gg) may deliver the same vote more than once.
+///
+/// Votes are keyed on their [`target`][crate::Vote::target], their voter and the time window they arrived in (the current and the previous window are both checked), and `vote.create` events are keyed on their [`id`][crate::VoteCreateEvent::id]. Test votes and other events are always handled.
+///
+/// A delivery is only stored once the wrapped handler has handled it successfully, so that [Top.gg](https://top.gg)'s next delivery of a vote that failed is handled again. While a vote is being handled, other deliveries of it fail with [`InFlight`][DedupError::InFlight], so that the webhook responds with a 5xx HTTP status code and [Top.gg](https://top.gg) delivers it again later.
+///
+/// If the [`DedupStore`] errors, the delivery is handled anyway, since handling a vote twice is preferred over losing it.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust,no_run
+/// use std::sync::Arc;
+/// use topgg::{Dedup, FileDedupStore, Vote, VoteHandler};
+///
+/// struct MyVoteHandler {}
+///
+/// #[async_trait::async_trait]
+/// impl VoteHandler for MyVoteHandler {
+///   async fn voted(&self, vote: Vote) {
+///     println!("{:?}", vote);
+///   }
+/// }
+///
+/// # fn run() -> std::io::Result<()> {
+/// let handler = Arc::new(Dedup::new(MyVoteHandler {}, FileDedupStore::open("votes.dedup")?));
+///
+/// // pass handler to any of the webhook wrappers...
+/// # Ok(())
+/// # }
+/// ```
+#[must_use]
+pub struct Dedup<H, S = MemoryDedupStore> {
+  handler: H,
+  store: S,
+  window: Duration,
+  in_flight: Mutex<HashSet<String>>,
+}
+
+impl<H, S> Dedup<H, S>
+where
+  S: DedupStore,
+{
+  /// Wraps the specified handler with the specified [`DedupStore`] and a time window of 1 hour.
+  #[inline(always)]
+  pub fn new(handler: H, store: S) -> Self {
+    Self {
+      handler,
+      store,
+      window: DEFAULT_WINDOW,
+      in_flight: Mutex::new(HashSet::new()),
+    }
+  }
+
+  /// Sets the time window that votes are keyed on. Since a user can only vote once every 12 hours, it should be shorter than 6 hours.
+  #[inline(always)]
+  pub fn window(mut self, window: Duration) -> Self {
+    self.window = window.max(Duration::from_secs(1));
+    self
+  }
+
+  /// The wrapped handler.
+  #[must_use]
+  #[inline(always)]
+  pub fn handler(&self) -> &H {
+    &self.handler
+  }
+
+  /// The [`DedupStore`] used by this wrapper.
+  #[must_use]
+  #[inline(always)]
+  pub fn store(&self) -> &S {
+    &self.store
+  }
+
+  /// Marks a delivery with the specified key as being handled until the returned guard is dropped, or returns `None` if it or a delivery with the specified previous key already is.
+  fn enter(&self, key: &str, previous_key: Option<&str>) -> Option<InFlight<'_>> {
+    let mut in_flight = self.in_flight.lock().unwrap();
+
+    if previous_key.is_some_and(|previous_key| in_flight.contains(previous_key))
+      || !in_flight.insert(key.to_owned())
+    {
+      return None;
+    }
+
+    Some(InFlight {
+      keys: &self.in_flight,
+      key: key.to_owned(),
+    })
+  }
+
+  /// Checks if a delivery with the specified key or, if any, previous key has already been handled.
+  async fn is_handled(&self, key: &str, previous_key: Option<&str>) -> bool {
+    if let Some(previous_key) = previous_key {
+      if matches!(self.store.contains(previous_key).await, Ok(true)) {
+        return true;
+      }
+    }
+
+    matches!(self.store.contains(key).await, Ok(true))
+  }
+
+  /// Returns the key of a vote that arrived at the specified amount of seconds since the Unix epoch, along with its key in the previous window if any.
+  fn vote_keys(&self, vote: &Vote, secs: u64) -> (String, Option<String>) {
+    let window = secs / self.window.as_secs();
+    let prefix = format!("vote:{}:{}:", vote.target.id(), vote.voter_id);
+
+    (
+      format!("{prefix}{window}"),
+      window
+        .checked_sub(1)
+        .map(|previous| format!("{prefix}{previous}")),
+    )
+  }
+}
+
+/// Marks a delivery as being handled until it's dropped.
+struct InFlight<'a> {
+  keys: &'a Mutex<HashSet<String>>,
+  key: String,
+}
+
+impl Drop for InFlight<'_> {
+  #[inline(always)]
+  fn drop(&mut self) {
+    self.keys.lock().unwrap().remove(&self.key);
+  }
+}
+
+#[async_trait::async_trait]
+impl<H, S> FallibleVoteHandler for Dedup<H, S>
+where
+  H: FallibleVoteHandler,
+  S: DedupStore,
+{
+  type Error = DedupError<H::Error>;
+
+  async fn try_voted(&self, vote: Vote) -> Result<(), Self::Error> {
+    if vote.is_test {
+      return self
+        .handler
+        .try_voted(vote)
+        .await
+        .map_err(DedupError::Handler);
+    }
+
+    let secs = SystemTime::now()
+      .duration_since(UNIX_EPOCH)
+      .unwrap_or_default()
+      .as_secs();
+
+    let (key, previous_key) = self.vote_keys(&vote, secs);
+
+    let Some(_in_flight) = self.enter(&key, previous_key.as_deref()) else {
+      return Err(DedupError::InFlight);
+    };
+
+    if self.is_handled(&key, previous_key.as_deref()).await {
+      return Ok(());
+    }
+
+    self
+      .handler
+      .try_voted(vote)
+      .await
+      .map_err(DedupError::Handler)?;
+
+    let _ = self.store.insert(&key).await;
+
+    Ok(())
+  }
+
+  #[inline(always)]
+  fn timeout(&self) -> Option<Duration> {
+    self.handler.timeout()
+  }
+}
+
+#[async_trait::async_trait]
+impl<H, S> WebhookEventHandler for Dedup<H, S>
+where
+  H: WebhookEventHandler,
+  S: DedupStore,
+{
+  async fn handle(&self, event: WebhookEvent) {
+    let key = match &event {
+      WebhookEvent::VoteCreate(vote) => format!("event:{}", vote.id),
+      _ => return self.handler.handle(event).await,
+    };
+
+    let Some(_in_flight) = self.enter(&key, None) else {
+      return;
+    };
+
+    if self.is_handled(&key, None).await {
+      return;
+    }
+
+    self.handler.handle(event).await;
+
+    let _ = self.store.insert(&key).await;
+  }
+}
+
+/// An error returned by a [`Dedup`] when handling a vote.
+#[derive(Debug)]
+pub enum DedupError<E> {
+  /// Another delivery of the same vote is still being handled.
+  InFlight,
+
+  /// The wrapped handler failed to handle the vote.
+  Handler(E),
+}
+
+impl<E> fmt::Display for DedupError<E>
+where
+  E: fmt::Display,
+{
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::InFlight => f.write_str("the vote is already being handled"),
+      Self::Handler(err) => write!(f, "{err}"),
+    }
+  }
+}
+
+impl<E> error::Error for DedupError<E>
+where
+  E: error::Error + 'static,
+{
+  #[inline(always)]
+  fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+    match self {
+      Self::InFlight => None,
+      Self::Handler(err) => Some(err),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::webhook::test_util::{vote, Counter};
+  use std::fs;
+  use tokio::sync::Notify;
+
+  #[derive(Default)]
+  struct Blocking {
+    entered: Notify,
+    released: Notify,
+  }
+
+  #[async_trait::async_trait]
+  impl FallibleVoteHandler for Blocking {
+    type Error = io::Error;
+
+    async fn try_voted(&self, _vote: Vote) -> Result<(), io::Error> {
+      self.entered.notify_one();
+      self.released.notified().await;
+
+      Ok(())
+    }
+  }
+
+  #[test]
+  fn lru_forgets_least_recently_used_keys() {
+    let mut lru = Lru::new(2);
+
+    assert!(lru.insert("a"));
+    assert!(lru.insert("b"));
+    assert!(!lru.insert("b"));
+    assert!(lru.insert("c"));
+
+    assert!(!lru.contains("a"));
+    assert_eq!(lru.iter().collect::<Vec<_>>(), ["b", "c"]);
+
+    assert!(lru.contains("b"));
+    assert!(lru.insert("d"));
+
+    assert!(!lru.contains("c"));
+    assert_eq!(lru.iter().collect::<Vec<_>>(), ["b", "d"]);
+
+    lru.remove("b");
+
+    assert!(!lru.contains("b"));
+    assert_eq!(lru.iter().collect::<Vec<_>>(), ["d"]);
+  }
+
+  #[tokio::test]
+  async fn file_store_replays_additions_and_removals() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("votes.dedup");
+
+    let store = FileDedupStore::open(&path).unwrap();
+
+    assert!(store.insert("a").await.unwrap());
+    assert!(store.insert("b").await.unwrap());
+    assert!(!store.insert("b").await.unwrap());
+    store.remove("a").await.unwrap();
+    drop(store);
+
+    let store = FileDedupStore::open(&path).unwrap();
+
+    assert!(!store.contains("a").await.unwrap());
+    assert!(store.contains("b").await.unwrap());
+  }
+
+  // holding the file's lock across the lookup is the point of this test.
+  #[allow(clippy::await_holding_lock)]
+  #[tokio::test]
+  async fn file_store_lookups_dont_wait_for_the_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = FileDedupStore::open(dir.path().join("votes.dedup")).unwrap();
+
+    store.insert("a").await.unwrap();
+
+    let _log = store.log.lock().unwrap();
+
+    assert!(store.contains("a").await.unwrap());
+  }
+
+  #[tokio::test]
+  async fn file_store_compacts_forgotten_keys() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("votes.dedup");
+
+    let store = FileDedupStore::with_capacity(&path, 2).unwrap();
+
+    for key in ["a", "b", "c", "d", "e"] {
+      store.insert(key).await.unwrap();
+    }
+
+    assert_eq!(fs::read_to_string(&path).unwrap(), "+d\n+e\n");
+    drop(store);
+
+    let store = FileDedupStore::with_capacity(&path, 2).unwrap();
+
+    assert!(!store.contains("c").await.unwrap());
+    assert!(store.contains("e").await.unwrap());
+  }
+
+  #[tokio::test]
+  async fn keys_votes_on_current_and_previous_window() {
+    let dedup = Dedup::new(Counter::default(), MemoryDedupStore::default());
+    let vote = vote("upvote");
+
+    assert_eq!(
+      dedup.vote_keys(&vote, 100),
+      (String::from("vote:1:2:0"), None)
+    );
+    assert_eq!(
+      dedup.vote_keys(&vote, 7300),
+      (String::from("vote:1:2:2"), Some(String::from("vote:1:2:1")))
+    );
+
+    dedup.store().insert("vote:1:2:1").await.unwrap();
+
+    assert!(dedup.is_handled("vote:1:2:2", Some("vote:1:2:1")).await);
+    assert!(!dedup.is_handled("vote:1:2:2", None).await);
+  }
+
+  #[tokio::test]
+  async fn stores_votes_after_success_only() {
+    let dedup = Dedup::new(Counter::default(), MemoryDedupStore::default());
+
+    dedup.handler().fail_next();
+
+    assert!(matches!(
+      dedup.try_voted(vote("upvote")).await,
+      Err(DedupError::Handler(_))
+    ));
+
+    dedup.try_voted(vote("upvote")).await.unwrap();
+    dedup.try_voted(vote("upvote")).await.unwrap();
+
+    assert_eq!(dedup.handler().calls(), 2);
+  }
+
+  #[tokio::test]
+  async fn always_handles_test_votes() {
+    let dedup = Dedup::new(Counter::default(), MemoryDedupStore::default());
+
+    dedup.try_voted(vote("test")).await.unwrap();
+    dedup.try_voted(vote("test")).await.unwrap();
+
+    assert_eq!(dedup.handler().calls(), 2);
+  }
+
+  #[tokio::test]
+  async fn fails_votes_in_flight() {
+    let dedup = Arc::new(Dedup::new(Blocking::default(), MemoryDedupStore::default()));
+
+    let first = tokio::spawn({
+      let dedup = Arc::clone(&dedup);
+
+      async move { dedup.try_voted(vote("upvote")).await }
+    });
+
+    dedup.handler().entered.notified().await;
+
+    assert!(matches!(
+      dedup.try_voted(vote("upvote")).await,
+      Err(DedupError::InFlight)
+    ));
+
+    dedup.handler().released.notify_one();
+    first.await.unwrap().unwrap();
+
+    // the first delivery was handled, so this one is skipped instead of blocking.
+    dedup.try_voted(vote("upvote")).await.unwrap();
+  }
+}
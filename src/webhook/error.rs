@@ -0,0 +1,77 @@
+use core::fmt;
+use std::error;
+
+/// An error coming from authenticating, parsing or handling an incoming [Top.gg](https://top.gg) webhook request.
+#[non_exhaustive]
+#[derive(Debug)]
+pub enum WebhookError {
+  /// The request has no valid `Authorization` header.
+  MissingAuthorization,
+
+  /// The request's `Authorization` header doesn't match any of the accepted passwords.
+  Unauthorized,
+
+  /// The delivery has no valid `x-topgg-signature` header.
+  MissingSignature,
+
+  /// The delivery's `x-topgg-signature` header is not in the `t={timestamp},v1={signature}` format.
+  MalformedSignature,
+
+  /// None of the delivery's signatures match any of the accepted secrets.
+  InvalidSignature,
+
+  /// The delivery's timestamp is outside of the accepted replay window. (See [`WebhookSecret::tolerance`][crate::WebhookSecret::tolerance])
+  Expired,
+
+  /// The request's body cannot be deserialized.
+  Payload(serde_json::Error),
+
+  /// The [`FallibleVoteHandler`][crate::FallibleVoteHandler] failed to handle the vote, with the error it returned.
+  Handler(Box<dyn error::Error + Send + Sync>),
+
+  /// The vote handler didn't finish within its [`timeout`][crate::FallibleVoteHandler::timeout].
+  Timeout,
+}
+
+impl WebhookError {
+  /// The HTTP status code that a webhook endpoint should respond with for this error, i.e. 400 (Bad Request) for [`Payload`][WebhookError::Payload], 500 (Internal Server Error) for [`Handler`][WebhookError::Handler], 503 (Service Unavailable) for [`Timeout`][WebhookError::Timeout] and 401 (Unauthorized) otherwise.
+  ///
+  /// [Top.gg](https://top.gg) retries deliveries that were answered with a 5xx status code.
+  #[must_use]
+  #[inline(always)]
+  pub const fn status_code(&self) -> u16 {
+    match self {
+      Self::Payload(_) => 400,
+      Self::Handler(_) => 500,
+      Self::Timeout => 503,
+      _ => 401,
+    }
+  }
+}
+
+impl fmt::Display for WebhookError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::MissingAuthorization => write!(f, "missing webhook authorization"),
+      Self::Unauthorized => write!(f, "unauthorized webhook request"),
+      Self::MissingSignature => write!(f, "missing webhook signature"),
+      Self::MalformedSignature => write!(f, "malformed webhook signature"),
+      Self::InvalidSignature => write!(f, "invalid webhook signature"),
+      Self::Expired => write!(f, "webhook timestamp is outside of the replay window"),
+      Self::Payload(err) => write!(f, "failed to deserialize webhook payload: {err}"),
+      Self::Handler(err) => write!(f, "vote handler failed: {err}"),
+      Self::Timeout => write!(f, "vote handler timed out"),
+    }
+  }
+}
+
+impl error::Error for WebhookError {
+  #[inline(always)]
+  fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+    match self {
+      Self::Payload(err) => Some(err),
+      Self::Handler(err) => Some(err.as_ref()),
+      _ => None,
+    }
+  }
+}
@@ -0,0 +1,185 @@
+use crate::{
+  platform::{Platform, ProjectType},
+  snowflake,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Deserializer};
+
+/// A struct representing the project that a signed [Top.gg](https://top.gg) webhook event is about.
+#[must_use]
+#[derive(Clone, Debug, Deserialize)]
+pub struct EventProject {
+  /// The [Top.gg](https://top.gg) ID of this project.
+  pub id: String,
+
+  /// The type of this project.
+  #[serde(rename = "type")]
+  pub project_type: ProjectType,
+
+  /// The platform of this project.
+  pub platform: Platform,
+
+  /// The platform-specific ID of this project, e.g. a Discord bot or server ID.
+  #[serde(deserialize_with = "snowflake::deserialize")]
+  pub platform_id: u64,
+}
+
+/// A struct representing the user that triggered a signed [Top.gg](https://top.gg) webhook event.
+#[must_use]
+#[derive(Clone, Debug, Deserialize)]
+pub struct EventUser {
+  /// The [Top.gg](https://top.gg) ID of this user.
+  pub id: String,
+
+  /// The platform-specific ID of this user, e.g. a Discord user ID.
+  #[serde(deserialize_with = "snowflake::deserialize")]
+  pub platform_id: u64,
+
+  /// The name of this user.
+  pub name: String,
+
+  /// A URL of this user's avatar image.
+  #[serde(default)]
+  pub avatar_url: Option<String>,
+}
+
+/// A struct representing a signed [Top.gg](https://top.gg) `vote.create` webhook event.
+#[must_use]
+#[derive(Clone, Debug, Deserialize)]
+pub struct VoteCreateEvent {
+  /// The [Top.gg](https://top.gg) ID of this vote.
+  pub id: String,
+
+  /// How many votes this vote counts as, e.g. `2` during the weekend multiplier.
+  #[serde(default = "default_weight")]
+  pub weight: u32,
+
+  /// When this vote was created.
+  pub created_at: DateTime<Utc>,
+
+  /// When this vote expires and the user can vote again.
+  pub expires_at: DateTime<Utc>,
+
+  /// The project that received this vote.
+  pub project: EventProject,
+
+  /// The user who voted.
+  pub user: EventUser,
+}
+
+/// A struct representing a signed [Top.gg](https://top.gg) `webhook.test` webhook event, sent from the project's webhook settings.
+#[must_use]
+#[derive(Clone, Debug, Deserialize)]
+pub struct TestEvent {
+  /// The project whose webhook is being tested.
+  pub project: EventProject,
+
+  /// The user who sent this test.
+  pub user: EventUser,
+}
+
+/// An enum representing a signed [Top.gg](https://top.gg) webhook event. (See [`WebhookSecret`][crate::WebhookSecret])
+#[must_use]
+#[non_exhaustive]
+#[derive(Clone, Debug)]
+pub enum WebhookEvent {
+  /// A user voted for the project. (`vote.create`)
+  VoteCreate(VoteCreateEvent),
+
+  /// The project's webhook is being tested. (`webhook.test`)
+  Test(TestEvent),
+
+  /// An event type that isn't known by this SDK yet, along with its raw data.
+  Unknown {
+    /// The event's type, e.g. `vote.create`.
+    event_type: String,
+
+    /// The event's raw data.
+    data: serde_json::Value,
+  },
+}
+
+impl<'de> Deserialize<'de> for WebhookEvent {
+  fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+  where
+    D: Deserializer<'de>,
+  {
+    use serde::de::Error;
+
+    #[derive(Deserialize)]
+    struct RawEvent {
+      #[serde(rename = "type")]
+      event_type: String,
+      #[serde(default)]
+      data: serde_json::Value,
+    }
+
+    let raw = RawEvent::deserialize(deserializer)?;
+
+    Ok(match raw.event_type.as_str() {
+      "vote.create" => {
+        Self::VoteCreate(serde_json::from_value(raw.data).map_err(D::Error::custom)?)
+      }
+      "webhook.test" => Self::Test(serde_json::from_value(raw.data).map_err(D::Error::custom)?),
+      _ => Self::Unknown {
+        event_type: raw.event_type,
+        data: raw.data,
+      },
+    })
+  }
+}
+
+#[inline(always)]
+const fn default_weight() -> u32 {
+  1
+}
+
+cfg_if::cfg_if! {
+  if #[cfg(any(feature = "actix-web", feature = "axum", feature = "rocket"))] {
+    use crate::{WebhookError, WebhookSecret};
+
+    /// A struct that represents an **unverified** signed request containing a [`WebhookEvent`] data.
+    ///
+    /// To verify this structure with a valid secret and consume the [`WebhookEvent`] data inside of it, see the [`verify`][IncomingEvent::verify] method.
+    #[must_use]
+    #[cfg_attr(docsrs, doc(cfg(any(feature = "actix-web", feature = "axum", feature = "rocket"))))]
+    #[derive(Clone)]
+    pub struct IncomingEvent {
+      pub(crate) signature: Option<String>,
+      pub(crate) body: Vec<u8>,
+    }
+
+    impl IncomingEvent {
+      /// Verifies this request's signature and timestamp with a secret, then parses it into a [`WebhookEvent`]. (See [`verify_and_parse_event`][crate::verify_and_parse_event])
+      ///
+      /// # Errors
+      ///
+      /// Errors if any of the following conditions are met:
+      /// - The request has no valid `x-topgg-signature` header ([`MissingSignature`][WebhookError::MissingSignature])
+      /// - The signature header is not in the `t={timestamp},v1={signature}` format ([`MalformedSignature`][WebhookError::MalformedSignature])
+      /// - The timestamp is outside of the replay window ([`Expired`][WebhookError::Expired])
+      /// - None of the signatures match any of the accepted secrets ([`InvalidSignature`][WebhookError::InvalidSignature])
+      /// - The body cannot be deserialized ([`Payload`][WebhookError::Payload])
+      #[inline(always)]
+      pub fn verify(self, secret: &WebhookSecret) -> Result<WebhookEvent, WebhookError> {
+        super::verify::parse_event(secret, self.signature.as_deref(), &self.body)
+      }
+    }
+  }
+}
+
+/// An async trait for adding an on-event handler for signed [Top.gg](https://top.gg) webhook deliveries to your application logic.
+///
+/// It's described as follows (without [`async_trait`]'s macro expansion):
+/// ```rust,no_run
+/// # use topgg::WebhookEvent;
+/// #[async_trait::async_trait]
+/// pub trait WebhookEventHandler: Send + Sync + 'static {
+///   async fn handle(&self, event: WebhookEvent);
+/// }
+/// ```
+#[async_trait::async_trait]
+pub trait WebhookEventHandler: Send + Sync + 'static {
+  /// Your event handler's on-event async callback. The endpoint will always return a 200 (OK) HTTP status code after running this method.
+  async fn handle(&self, event: WebhookEvent);
+}
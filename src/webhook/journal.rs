@@ -0,0 +1,344 @@
+use super::log_file::{self, LogFile};
+use crate::{FallibleVoteHandler, Vote};
+use core::{fmt, time::Duration};
+use serde::{Deserialize, Serialize};
+use std::{
+  collections::BTreeMap,
+  error, io,
+  path::Path,
+  sync::{Arc, Mutex},
+};
+
+const COMPACT_THRESHOLD: usize = 1000;
+
+#[derive(Deserialize, Serialize)]
+#[serde(untagged)]
+enum Entry {
+  Vote { id: u64, vote: Vote },
+  Done { done: u64 },
+}
+
+#[derive(Debug)]
+struct JournalState {
+  log: LogFile,
+  next_id: u64,
+  pending: BTreeMap<u64, String>,
+}
+
+impl JournalState {
+  /// Appends a vote to the journal and waits for it to reach the disk. Returns the ID of the new entry.
+  fn append(&mut self, vote: Vote) -> io::Result<u64> {
+    let id = self.next_id;
+    let line = serde_json::to_string(&Entry::Vote { id, vote })?;
+
+    self.log.append(&line)?;
+    self.log.sync()?;
+
+    self.next_id += 1;
+    self.pending.insert(id, line);
+
+    Ok(id)
+  }
+
+  /// Marks an entry as done.
+  fn finish(&mut self, id: u64) -> io::Result<()> {
+    if self.pending.remove(&id).is_some() {
+      self
+        .log
+        .append(&serde_json::to_string(&Entry::Done { done: id })?)?;
+
+      if self.log.lines() > COMPACT_THRESHOLD.max(self.pending.len() * 2) {
+        self.compact()?;
+      }
+    }
+
+    Ok(())
+  }
+
+  /// Rewrites the file with only the entries that are not done yet.
+  #[inline(always)]
+  fn compact(&mut self) -> io::Result<()> {
+    self.log.compact(self.pending.values())
+  }
+}
+
+/// A wrapper around a [`VoteHandler`][crate::VoteHandler] or a [`FallibleVoteHandler`] that writes every incoming [`Vote`] to a journal file before handling it, so that no vote is lost if the process stops before the handler finishes.
+///
+/// Each vote is appended to the file and synced to the disk before the wrapped handler is called, and thus before the webhook responds to [Top.gg](https://top.gg). It is only marked as done after the wrapped handler returns. Votes that were never marked as done are kept in the file and can be handled again with [`replay`][Journal::replay] on the next startup, so the wrapped handler may receive the same vote more than once. (See [`Dedup`][crate::Dedup])
+///
+/// If a vote cannot be written to the journal file, it is not handled and [`Io`][JournalError::Io] is returned instead, so that the webhook responds with a 5xx HTTP status code and [Top.gg](https://top.gg) delivers it again later. If a [`FallibleVoteHandler`] fails to handle a vote, it is marked as done as well for the same reason.
+///
+/// File operations run on [`tokio`]'s blocking thread pool, so that syncing to the disk doesn't stall other deliveries.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust,no_run
+/// use std::sync::Arc;
+/// use topgg::{Journal, Vote, VoteHandler};
+///
+/// struct MyVoteHandler {}
+///
+/// #[async_trait::async_trait]
+/// impl VoteHandler for MyVoteHandler {
+///   async fn voted(&self, vote: Vote) {
+///     println!("{:?}", vote);
+///   }
+/// }
+///
+/// # async fn run() -> std::io::Result<()> {
+/// let handler = Arc::new(Journal::open("votes.journal", MyVoteHandler {})?);
+///
+/// // handle votes left unfinished by the previous run
+/// handler.replay().await?;
+///
+/// // pass handler to any of the webhook wrappers...
+/// # Ok(())
+/// # }
+/// ```
+#[must_use]
+pub struct Journal<H> {
+  handler: H,
+  state: Arc<Mutex<JournalState>>,
+  unfinished: Mutex<Vec<(u64, Vote)>>,
+}
+
+impl<H> Journal<H>
+where
+  H: FallibleVoteHandler,
+{
+  /// Opens or creates a journal file at the specified path and wraps the specified handler with it.
+  ///
+  /// Votes left unfinished in the file are not handled until [`replay`][Journal::replay] is called. Lines that cannot be read, such as one cut short by a crash, are ignored.
+  ///
+  /// # Errors
+  ///
+  /// Errors if the file cannot be read or created.
+  pub fn open<P>(path: P, handler: H) -> io::Result<Self>
+  where
+    P: AsRef<Path>,
+  {
+    let mut pending = BTreeMap::new();
+    let mut unfinished = BTreeMap::new();
+    let mut next_id = 0;
+
+    let log = LogFile::open(path, |line| match serde_json::from_str(&line) {
+      Ok(Entry::Vote { id, vote }) => {
+        next_id = next_id.max(id + 1);
+        unfinished.insert(id, vote);
+        pending.insert(id, line);
+      }
+
+      Ok(Entry::Done { done }) => {
+        unfinished.remove(&done);
+        pending.remove(&done);
+      }
+
+      Err(_) => {}
+    })?;
+
+    let mut state = JournalState {
+      log,
+      next_id,
+      pending,
+    };
+
+    // a crash may have left a partially written line at the end of the file.
+    state.compact()?;
+
+    Ok(Self {
+      handler,
+      state: Arc::new(Mutex::new(state)),
+      unfinished: Mutex::new(unfinished.into_iter().collect()),
+    })
+  }
+
+  /// Handles every vote that the journal file had left unfinished when it was opened, in the order they arrived. Returns the amount of votes handled successfully.
+  ///
+  /// Votes that the wrapped handler fails to handle are left unfinished until the next time the journal file is opened, since [Top.gg](https://top.gg) won't deliver them again.
+  ///
+  /// This should be called once on startup, before or while the webhook starts receiving new votes.
+  ///
+  /// # Errors
+  ///
+  /// Errors if a vote cannot be marked as done in the journal file.
+  pub async fn replay(&self) -> io::Result<usize> {
+    let unfinished = core::mem::take(&mut *self.unfinished.lock().unwrap());
+    let mut count = 0;
+
+    for (id, vote) in unfinished {
+      if self.handler.try_voted(vote).await.is_ok() {
+        self.with_state(move |state| state.finish(id)).await?;
+        count += 1;
+      }
+    }
+
+    Ok(count)
+  }
+
+  /// Runs a blocking operation on the journal file away from the async runtime.
+  #[inline(always)]
+  async fn with_state<T, F>(&self, operation: F) -> io::Result<T>
+  where
+    T: Send + 'static,
+    F: FnOnce(&mut JournalState) -> io::Result<T> + Send + 'static,
+  {
+    log_file::run_blocking(&self.state, operation).await
+  }
+
+  /// The amount of votes that are not marked as done yet, including the ones being handled right now.
+  #[must_use]
+  #[inline(always)]
+  pub fn pending(&self) -> usize {
+    self.state.lock().unwrap().pending.len()
+  }
+
+  /// The wrapped handler.
+  #[must_use]
+  #[inline(always)]
+  pub fn handler(&self) -> &H {
+    &self.handler
+  }
+}
+
+#[async_trait::async_trait]
+impl<H> FallibleVoteHandler for Journal<H>
+where
+  H: FallibleVoteHandler,
+{
+  type Error = JournalError<H::Error>;
+
+  async fn try_voted(&self, vote: Vote) -> Result<(), Self::Error> {
+    let entry = vote.clone();
+    let id = self
+      .with_state(move |state| state.append(entry))
+      .await
+      .map_err(JournalError::Io)?;
+
+    let result = self.handler.try_voted(vote).await;
+
+    // if this fails, the vote is handled again on the next replay.
+    let _ = self.with_state(move |state| state.finish(id)).await;
+
+    result.map_err(JournalError::Handler)
+  }
+
+  #[inline(always)]
+  fn timeout(&self) -> Option<Duration> {
+    self.handler.timeout()
+  }
+}
+
+/// An error returned by a [`Journal`] when handling a vote.
+#[derive(Debug)]
+pub enum JournalError<E> {
+  /// The vote couldn't be written to the journal file, so it was not handled.
+  Io(io::Error),
+
+  /// The wrapped handler failed to handle the vote.
+  Handler(E),
+}
+
+impl<E> fmt::Display for JournalError<E>
+where
+  E: fmt::Display,
+{
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::Io(err) => write!(f, "failed to write vote to journal: {err}"),
+      Self::Handler(err) => write!(f, "{err}"),
+    }
+  }
+}
+
+impl<E> error::Error for JournalError<E>
+where
+  E: error::Error + 'static,
+{
+  #[inline(always)]
+  fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+    match self {
+      Self::Io(err) => Some(err),
+      Self::Handler(err) => Some(err),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::webhook::test_util::{vote, Counter};
+
+  #[tokio::test]
+  async fn replays_unfinished_votes() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("votes.journal");
+
+    let journal = Journal::open(&path, Counter::default()).unwrap();
+
+    journal.try_voted(vote("upvote")).await.unwrap();
+    journal
+      .state
+      .lock()
+      .unwrap()
+      .append(vote("upvote"))
+      .unwrap();
+
+    assert_eq!(journal.pending(), 1);
+    drop(journal);
+
+    let journal = Journal::open(&path, Counter::default()).unwrap();
+
+    assert_eq!(journal.replay().await.unwrap(), 1);
+    assert_eq!(journal.handler().calls(), 1);
+    assert_eq!(journal.pending(), 0);
+    drop(journal);
+
+    let journal = Journal::open(&path, Counter::default()).unwrap();
+
+    assert_eq!(journal.replay().await.unwrap(), 0);
+  }
+
+  #[tokio::test]
+  async fn keeps_failed_replays_unfinished() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("votes.journal");
+
+    let journal = Journal::open(&path, Counter::default()).unwrap();
+
+    journal
+      .state
+      .lock()
+      .unwrap()
+      .append(vote("upvote"))
+      .unwrap();
+    drop(journal);
+
+    let journal = Journal::open(&path, Counter::default()).unwrap();
+
+    journal.handler().fail_next();
+
+    assert_eq!(journal.replay().await.unwrap(), 0);
+    drop(journal);
+
+    let journal = Journal::open(&path, Counter::default()).unwrap();
+
+    assert_eq!(journal.replay().await.unwrap(), 1);
+  }
+
+  #[tokio::test]
+  async fn compacts_finished_votes() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("votes.journal");
+
+    let journal = Journal::open(&path, Counter::default()).unwrap();
+
+    // every vote takes up two lines until the file is compacted.
+    for _ in 0..=COMPACT_THRESHOLD / 2 {
+      journal.try_voted(vote("upvote")).await.unwrap();
+    }
+
+    assert_eq!(journal.state.lock().unwrap().log.lines(), 0);
+  }
+}
@@ -0,0 +1,156 @@
+use std::{
+  fmt::Display,
+  fs::{self, File, OpenOptions},
+  io::{self, BufRead, BufReader, Write},
+  path::{Path, PathBuf},
+  sync::{Arc, Mutex},
+};
+
+/// An append-only file of lines that can be compacted, shared by [`Journal`][crate::Journal] and [`FileDedupStore`][crate::FileDedupStore].
+#[derive(Debug)]
+pub(crate) struct LogFile {
+  path: PathBuf,
+  file: File,
+  lines: usize,
+}
+
+impl LogFile {
+  /// Opens or creates a log file at the specified path, passing every line already in it to `read`.
+  pub(crate) fn open<P, F>(path: P, mut read: F) -> io::Result<Self>
+  where
+    P: AsRef<Path>,
+    F: FnMut(String),
+  {
+    let path = path.as_ref().to_path_buf();
+    let file = OpenOptions::new()
+      .create(true)
+      .read(true)
+      .append(true)
+      .open(&path)?;
+
+    let mut lines = 0;
+
+    for line in BufReader::new(&file).lines() {
+      read(line?);
+      lines += 1;
+    }
+
+    Ok(Self { path, file, lines })
+  }
+
+  /// The amount of lines in the file.
+  #[inline(always)]
+  pub(crate) fn lines(&self) -> usize {
+    self.lines
+  }
+
+  /// Appends a line to the file. It must not contain newlines.
+  pub(crate) fn append(&mut self, line: &str) -> io::Result<()> {
+    self.file.write_all(format!("{line}\n").as_bytes())?;
+    self.lines += 1;
+
+    Ok(())
+  }
+
+  /// Waits for every appended line to reach the disk.
+  #[inline(always)]
+  pub(crate) fn sync(&self) -> io::Result<()> {
+    self.file.sync_data()
+  }
+
+  /// Replaces the file's contents with the specified lines.
+  ///
+  /// They are written to a temporary file next to it first, so that a crash halfway leaves the old file intact.
+  pub(crate) fn compact<I>(&mut self, lines: I) -> io::Result<()>
+  where
+    I: IntoIterator,
+    I::Item: Display,
+  {
+    let mut temp = self.path.clone().into_os_string();
+    temp.push(".tmp");
+
+    let mut file = File::create(&temp)?;
+    let mut count = 0;
+
+    for line in lines {
+      writeln!(file, "{line}")?;
+      count += 1;
+    }
+
+    file.sync_all()?;
+    fs::rename(&temp, &self.path)?;
+
+    self.file = OpenOptions::new().append(true).open(&self.path)?;
+    self.lines = count;
+
+    Ok(())
+  }
+}
+
+/// Runs a blocking operation on state shared with other tasks on [`tokio`]'s blocking thread pool, away from the async runtime.
+pub(crate) async fn run_blocking<S, T, F>(state: &Arc<Mutex<S>>, operation: F) -> io::Result<T>
+where
+  S: Send + 'static,
+  T: Send + 'static,
+  F: FnOnce(&mut S) -> io::Result<T> + Send + 'static,
+{
+  let state = Arc::clone(state);
+
+  tokio::task::spawn_blocking(move || operation(&mut state.lock().unwrap()))
+    .await
+    .map_err(io::Error::other)?
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn read(path: &Path) -> Vec<String> {
+    let mut lines = Vec::new();
+
+    LogFile::open(path, |line| lines.push(line)).unwrap();
+
+    lines
+  }
+
+  #[test]
+  fn reads_appended_lines_on_reopen() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("votes.log");
+
+    let mut log = LogFile::open(&path, |_| {}).unwrap();
+
+    log.append("a").unwrap();
+    log.append("b").unwrap();
+    log.sync().unwrap();
+
+    assert_eq!(log.lines(), 2);
+    drop(log);
+
+    assert_eq!(read(&path), ["a", "b"]);
+    assert_eq!(LogFile::open(&path, |_| {}).unwrap().lines(), 2);
+  }
+
+  #[test]
+  fn compacts_next_to_the_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("votes.log");
+    let other = dir.path().join("votes.tmp");
+
+    fs::write(&other, "unrelated").unwrap();
+
+    let mut log = LogFile::open(&path, |_| {}).unwrap();
+
+    for line in ["a", "b", "c"] {
+      log.append(line).unwrap();
+    }
+
+    log.compact(["c"]).unwrap();
+    log.append("d").unwrap();
+
+    assert_eq!(log.lines(), 2);
+    assert_eq!(read(&path), ["c", "d"]);
+    assert_eq!(fs::read_to_string(&other).unwrap(), "unrelated");
+    assert!(!dir.path().join("votes.log.tmp").exists());
+  }
+}
@@ -1,12 +1,53 @@
+mod auth;
+mod dedup;
+mod error;
+mod event;
+mod journal;
+mod log_file;
+mod router;
+mod signature;
+#[cfg(test)]
+mod test_util;
+mod verify;
 mod vote;
+
+#[cfg_attr(docsrs, doc(cfg(feature = "webhook")))]
+pub use auth::Passwords;
+#[cfg_attr(docsrs, doc(cfg(feature = "webhook")))]
+pub use dedup::{Dedup, DedupError, DedupStore, FileDedupStore, MemoryDedupStore};
+#[cfg_attr(docsrs, doc(cfg(feature = "webhook")))]
+pub use error::WebhookError;
+#[cfg_attr(docsrs, doc(cfg(feature = "webhook")))]
+pub use event::*;
+#[cfg_attr(docsrs, doc(cfg(feature = "webhook")))]
+pub use journal::{Journal, JournalError};
+#[cfg_attr(docsrs, doc(cfg(feature = "webhook")))]
+pub use router::VoteRouter;
+#[cfg_attr(docsrs, doc(cfg(feature = "webhook")))]
+pub use signature::{WebhookSecret, SIGNATURE_HEADER};
+#[cfg_attr(docsrs, doc(cfg(feature = "webhook")))]
+pub use verify::{handle_vote, verify_and_parse, verify_and_parse_event};
 #[cfg_attr(docsrs, doc(cfg(feature = "webhook")))]
 pub use vote::*;
 
-#[cfg(feature = "actix-web")]
-mod actix_web;
+#[cfg_attr(docsrs, doc(cfg(feature = "webhook")))]
+pub use crate::platform::{Platform, ProjectType};
 
-#[cfg(feature = "rocket")]
-mod rocket;
+cfg_if::cfg_if! {
+  if #[cfg(feature = "actix-web")] {
+    /// Wrapper for working with the [`actix-web`](https://actix.rs) web framework.
+    #[cfg_attr(docsrs, doc(cfg(feature = "actix-web")))]
+    pub mod actix_web;
+  }
+}
+
+cfg_if::cfg_if! {
+  if #[cfg(feature = "rocket")] {
+    /// Wrapper for working with the [`rocket`](https://rocket.rs) web framework.
+    #[cfg_attr(docsrs, doc(cfg(feature = "rocket")))]
+    pub mod rocket;
+  }
+}
 
 cfg_if::cfg_if! {
   if #[cfg(feature = "axum")] {
@@ -23,3 +64,19 @@ cfg_if::cfg_if! {
     pub mod warp;
   }
 }
+
+cfg_if::cfg_if! {
+  if #[cfg(feature = "tower")] {
+    /// Wrapper for working with [`tower`](https://crates.io/crates/tower) services.
+    #[cfg_attr(docsrs, doc(cfg(feature = "tower")))]
+    pub mod tower;
+  }
+}
+
+cfg_if::cfg_if! {
+  if #[cfg(feature = "server")] {
+    /// A standalone webhook server built directly on [`hyper`](https://crates.io/crates/hyper), for bots without a web framework.
+    #[cfg_attr(docsrs, doc(cfg(feature = "server")))]
+    pub mod server;
+  }
+}
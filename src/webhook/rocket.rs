@@ -1,28 +1,234 @@
-use crate::{IncomingVote, Vote};
+use super::verify::Incoming;
+use crate::{
+  FallibleVoteHandler, IncomingEvent, IncomingVote, Passwords, WebhookEventHandler, WebhookSecret,
+};
 use rocket::{
-  data::{Data, FromData, Outcome},
-  http::Status,
+  data::{Data, FromData, Limits, Outcome},
+  http::{Method, Status},
   request::Request,
-  serde::json::Json,
+  route::{self, Handler, Route},
 };
+use std::sync::Arc;
+
+async fn from_data<'r, T>(request: &'r Request<'_>, data: Data<'r>) -> Outcome<'r, T, ()>
+where
+  T: Incoming,
+{
+  let limit = request.limits().get("json").unwrap_or(Limits::JSON);
+
+  match data.open(limit).into_bytes().await {
+    Ok(body) if body.is_complete() => Outcome::Success(T::new(
+      request.headers().get_one(T::HEADER).map(String::from),
+      body.into_inner(),
+    )),
+
+    Ok(_) => Outcome::Error((Status::PayloadTooLarge, ())),
+    Err(_) => Outcome::Error((Status::BadRequest, ())),
+  }
+}
 
 #[cfg_attr(docsrs, doc(cfg(feature = "rocket")))]
 #[rocket::async_trait]
 impl<'r> FromData<'r> for IncomingVote {
   type Error = ();
 
+  #[inline(always)]
   async fn from_data(request: &'r Request<'_>, data: Data<'r>) -> Outcome<'r, Self> {
-    let headers = request.headers();
-
-    if let Some(authorization) = headers.get_one("Authorization") {
-      if let Outcome::Success(vote) = <Json<Vote> as FromData>::from_data(request, data).await {
-        return Outcome::Success(Self {
-          authorization: authorization.to_owned(),
-          vote: vote.into_inner(),
-        });
+    from_data(request, data).await
+  }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "rocket")))]
+#[rocket::async_trait]
+impl<'r> FromData<'r> for IncomingEvent {
+  type Error = ();
+
+  #[inline(always)]
+  async fn from_data(request: &'r Request<'_>, data: Data<'r>) -> Outcome<'r, Self> {
+    from_data(request, data).await
+  }
+}
+
+/// A `rocket` route [`Handler`] for adding an on-vote event handler to your application logic. (See [`webhook`])
+#[must_use]
+pub struct VoteWebhook<T> {
+  state: Arc<T>,
+  passwords: Arc<Passwords>,
+  rank: Option<isize>,
+}
+
+impl<T> VoteWebhook<T> {
+  /// Sets the rank of the mounted route.
+  #[inline(always)]
+  pub fn rank(mut self, rank: isize) -> Self {
+    self.rank = Some(rank);
+    self
+  }
+}
+
+impl<T> Clone for VoteWebhook<T> {
+  #[inline(always)]
+  fn clone(&self) -> Self {
+    Self {
+      state: Arc::clone(&self.state),
+      passwords: Arc::clone(&self.passwords),
+      rank: self.rank,
+    }
+  }
+}
+
+#[rocket::async_trait]
+impl<T> Handler for VoteWebhook<T>
+where
+  T: FallibleVoteHandler,
+{
+  async fn handle<'r>(&self, request: &'r Request<'_>, data: Data<'r>) -> route::Outcome<'r> {
+    let vote = match IncomingVote::from_data(request, data).await {
+      Outcome::Success(vote) => vote,
+      Outcome::Error((status, ())) => return route::Outcome::Error(status),
+      Outcome::Forward((data, status)) => return route::Outcome::Forward((data, status)),
+    };
+
+    let result = match vote.verify(&self.passwords) {
+      Ok(vote) => crate::handle_vote(&self.state, vote).await,
+      Err(err) => Err(err),
+    };
+
+    match result {
+      Ok(()) => route::Outcome::from(request, Status::Ok),
+      Err(err) => route::Outcome::Error(Status::new(err.status_code())),
+    }
+  }
+}
+
+impl<T> From<VoteWebhook<T>> for Vec<Route>
+where
+  T: FallibleVoteHandler,
+{
+  #[inline(always)]
+  fn from(webhook: VoteWebhook<T>) -> Self {
+    vec![Route::ranked(webhook.rank, Method::Post, "/", webhook)]
+  }
+}
+
+/// A `rocket` route [`Handler`] for adding an on-event handler for signed [Top.gg](https://top.gg) webhook deliveries to your application logic. (See [`webhook_v1`])
+#[must_use]
+pub struct EventWebhook<T> {
+  state: Arc<T>,
+  secret: Arc<WebhookSecret>,
+  rank: Option<isize>,
+}
+
+impl<T> EventWebhook<T> {
+  /// Sets the rank of the mounted route.
+  #[inline(always)]
+  pub fn rank(mut self, rank: isize) -> Self {
+    self.rank = Some(rank);
+    self
+  }
+}
+
+impl<T> Clone for EventWebhook<T> {
+  #[inline(always)]
+  fn clone(&self) -> Self {
+    Self {
+      state: Arc::clone(&self.state),
+      secret: Arc::clone(&self.secret),
+      rank: self.rank,
+    }
+  }
+}
+
+#[rocket::async_trait]
+impl<T> Handler for EventWebhook<T>
+where
+  T: WebhookEventHandler,
+{
+  async fn handle<'r>(&self, request: &'r Request<'_>, data: Data<'r>) -> route::Outcome<'r> {
+    let event = match IncomingEvent::from_data(request, data).await {
+      Outcome::Success(event) => event,
+      Outcome::Error((status, ())) => return route::Outcome::Error(status),
+      Outcome::Forward((data, status)) => return route::Outcome::Forward((data, status)),
+    };
+
+    match event.verify(&self.secret) {
+      Ok(event) => {
+        self.state.handle(event).await;
+
+        route::Outcome::from(request, Status::Ok)
       }
+
+      Err(err) => route::Outcome::Error(Status::new(err.status_code())),
     }
+  }
+}
+
+impl<T> From<EventWebhook<T>> for Vec<Route>
+where
+  T: WebhookEventHandler,
+{
+  #[inline(always)]
+  fn from(webhook: EventWebhook<T>) -> Self {
+    vec![Route::ranked(webhook.rank, Method::Post, "/", webhook)]
+  }
+}
 
-    Outcome::Error((Status::Unauthorized, ()))
+/// Creates a new `rocket` route for adding an on-vote event handler to your application logic, to be mounted at the path of your choice. (See [`Passwords`])
+///
+/// `state` can also be a [`FallibleVoteHandler`].
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust,no_run
+/// use std::sync::Arc;
+/// use topgg::{Vote, VoteHandler};
+///
+/// struct MyVoteHandler {}
+///
+/// #[async_trait::async_trait]
+/// impl VoteHandler for MyVoteHandler {
+///   async fn voted(&self, vote: Vote) {
+///     println!("{:?}", vote);
+///   }
+/// }
+///
+/// # fn run() {
+/// let state = Arc::new(MyVoteHandler {});
+///
+/// // POST /webhook
+/// let rocket = rocket::build().mount(
+///   "/webhook",
+///   topgg::rocket::webhook("my-webhook-password", Arc::clone(&state)),
+/// );
+/// # }
+/// ```
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(feature = "rocket")))]
+pub fn webhook<P, T>(passwords: P, state: Arc<T>) -> VoteWebhook<T>
+where
+  P: Into<Passwords>,
+  T: FallibleVoteHandler,
+{
+  VoteWebhook {
+    state,
+    passwords: Arc::new(passwords.into()),
+    rank: None,
+  }
+}
+
+/// Creates a new `rocket` route for adding an on-event handler for signed [Top.gg](https://top.gg) webhook deliveries to your application logic, to be mounted at the path of your choice. (See [`webhook`])
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(feature = "rocket")))]
+pub fn webhook_v1<S, T>(secret: S, state: Arc<T>) -> EventWebhook<T>
+where
+  S: Into<WebhookSecret>,
+  T: WebhookEventHandler,
+{
+  EventWebhook {
+    state,
+    secret: Arc::new(secret.into()),
+    rank: None,
   }
 }
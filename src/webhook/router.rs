@@ -0,0 +1,153 @@
+use super::verify::handle_vote;
+use crate::{FallibleVoteHandler, Vote, VoteTarget, WebhookError};
+use std::sync::Arc;
+
+/// A handler that dispatches votes for a Discord bot and votes for a Discord server to different handlers, so that both can share a single webhook endpoint. (See [`VoteTarget`])
+///
+/// Both handlers can be any [`VoteHandler`][crate::VoteHandler] or [`FallibleVoteHandler`]. Each vote is handled the same way [`handle_vote`][crate::handle_vote] would, with the [`timeout`][FallibleVoteHandler::timeout] of the handler it's dispatched to, so the router itself has none.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust,no_run
+/// use std::sync::Arc;
+/// use topgg::{Vote, VoteHandler, VoteRouter};
+///
+/// struct BotVoteHandler {}
+///
+/// #[async_trait::async_trait]
+/// impl VoteHandler for BotVoteHandler {
+///   async fn voted(&self, vote: Vote) {
+///     println!("{} voted for my bot!", vote.voter_id);
+///   }
+/// }
+///
+/// struct ServerVoteHandler {}
+///
+/// #[async_trait::async_trait]
+/// impl VoteHandler for ServerVoteHandler {
+///   async fn voted(&self, vote: Vote) {
+///     println!("{} voted for my server!", vote.voter_id);
+///   }
+/// }
+///
+/// let handler = Arc::new(VoteRouter::new(BotVoteHandler {}, ServerVoteHandler {}));
+///
+/// // pass handler to any of the webhook wrappers...
+/// ```
+#[must_use]
+pub struct VoteRouter<B, S> {
+  bot: Arc<B>,
+  server: Arc<S>,
+}
+
+impl<B, S> VoteRouter<B, S>
+where
+  B: FallibleVoteHandler,
+  S: FallibleVoteHandler,
+{
+  /// Creates a brand new [`VoteRouter`] that dispatches votes for a Discord bot to `bot` and votes for a Discord server to `server`.
+  #[inline(always)]
+  pub fn new(bot: B, server: S) -> Self {
+    Self {
+      bot: Arc::new(bot),
+      server: Arc::new(server),
+    }
+  }
+
+  /// The handler for votes for a Discord bot.
+  #[must_use]
+  #[inline(always)]
+  pub fn bot(&self) -> &B {
+    &self.bot
+  }
+
+  /// The handler for votes for a Discord server.
+  #[must_use]
+  #[inline(always)]
+  pub fn server(&self) -> &S {
+    &self.server
+  }
+}
+
+#[async_trait::async_trait]
+impl<B, S> FallibleVoteHandler for VoteRouter<B, S>
+where
+  B: FallibleVoteHandler,
+  S: FallibleVoteHandler,
+{
+  type Error = WebhookError;
+
+  async fn try_voted(&self, vote: Vote) -> Result<(), WebhookError> {
+    match vote.target {
+      VoteTarget::Bot(_) => handle_vote(&self.bot, vote).await,
+      VoteTarget::Server(_) => handle_vote(&self.server, vote).await,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::webhook::test_util::{vote, Counter};
+  use core::time::Duration;
+
+  struct Slow(Duration);
+
+  #[async_trait::async_trait]
+  impl crate::VoteHandler for Slow {
+    async fn voted(&self, _vote: Vote) {
+      tokio::time::sleep(Duration::from_secs(1)).await;
+    }
+
+    fn timeout(&self) -> Option<Duration> {
+      Some(self.0)
+    }
+  }
+
+  fn server_vote() -> Vote {
+    serde_json::from_str(r#"{"guild":"3","user":"2","type":"upvote"}"#).unwrap()
+  }
+
+  #[tokio::test]
+  async fn dispatches_on_target() {
+    let router = Arc::new(VoteRouter::new(Counter::default(), Counter::default()));
+
+    handle_vote(&router, vote("upvote")).await.unwrap();
+    handle_vote(&router, server_vote()).await.unwrap();
+    handle_vote(&router, server_vote()).await.unwrap();
+
+    assert_eq!(router.bot().calls(), 1);
+    assert_eq!(router.server().calls(), 2);
+  }
+
+  #[tokio::test]
+  async fn reports_handler_errors() {
+    let router = Arc::new(VoteRouter::new(Counter::default(), Counter::default()));
+
+    router.server().fail_next();
+
+    let err = handle_vote(&router, server_vote()).await.unwrap_err();
+
+    assert!(matches!(err, WebhookError::Handler(_)));
+    assert_eq!(err.status_code(), 500);
+  }
+
+  #[tokio::test]
+  async fn applies_each_handlers_timeout() {
+    let router = Arc::new(VoteRouter::new(
+      Slow(Duration::from_millis(10)),
+      Counter::default(),
+    ));
+
+    assert_eq!(router.timeout(), None);
+
+    let err = handle_vote(&router, vote("upvote")).await.unwrap_err();
+
+    assert!(matches!(err, WebhookError::Timeout));
+    assert_eq!(err.status_code(), 503);
+
+    handle_vote(&router, server_vote()).await.unwrap();
+  }
+}
@@ -0,0 +1,217 @@
+use crate::{
+  tower::{EventService, VoteService},
+  FallibleVoteHandler, Passwords, WebhookEventHandler, WebhookSecret,
+};
+use bytes::Bytes;
+use core::{
+  convert::Infallible,
+  future::{self, Future, IntoFuture},
+  pin::Pin,
+  time::Duration,
+};
+use http::{Request, Response};
+use http_body_util::Full;
+use hyper::{body::Incoming, server::conn::http1};
+use hyper_util::{
+  rt::{TokioIo, TokioTimer},
+  server::graceful::GracefulShutdown,
+  service::TowerToHyperService,
+};
+use std::{io, net::SocketAddr, sync::Arc};
+use tokio::{net::TcpListener, spawn};
+use tower_service::Service;
+
+#[cfg(feature = "server-rustls")]
+use tokio_rustls::{rustls::ServerConfig, TlsAcceptor};
+
+/// How long a connection may take to finish its TLS handshake or to send its request headers before being closed.
+const HEADER_READ_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How long to wait before accepting connections again after failing to, e.g. after running out of file descriptors.
+const ACCEPT_BACKOFF: Duration = Duration::from_millis(100);
+
+type Shutdown = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+/// A standalone webhook server built directly on [`hyper`](https://crates.io/crates/hyper), returned by [`serve`] and [`serve_events`].
+///
+/// It starts listening once `.await`ed, and only resolves once the server stops, i.e. after a graceful shutdown.
+#[must_use]
+pub struct Serve<S> {
+  addr: SocketAddr,
+  service: S,
+  shutdown: Option<Shutdown>,
+  #[cfg(feature = "server-rustls")]
+  tls: Option<Arc<ServerConfig>>,
+}
+
+impl<S> Serve<S>
+where
+  S: Service<Request<Incoming>, Response = Response<Full<Bytes>>, Error = Infallible>
+    + Clone
+    + Send
+    + 'static,
+  S::Future: Send,
+{
+  #[inline(always)]
+  fn new(addr: SocketAddr, service: S) -> Self {
+    Self {
+      addr,
+      service,
+      shutdown: None,
+      #[cfg(feature = "server-rustls")]
+      tls: None,
+    }
+  }
+
+  /// Stops accepting new connections once the specified future completes, then waits for every in-flight request to finish before resolving.
+  #[inline(always)]
+  pub fn with_graceful_shutdown<F>(mut self, signal: F) -> Self
+  where
+    F: Future<Output = ()> + Send + 'static,
+  {
+    self.shutdown = Some(Box::pin(signal));
+    self
+  }
+
+  /// Serves HTTPS instead of plain HTTP with the specified [`rustls`](https://crates.io/crates/rustls) configuration.
+  #[inline(always)]
+  #[cfg(feature = "server-rustls")]
+  #[cfg_attr(docsrs, doc(cfg(feature = "server-rustls")))]
+  pub fn tls(mut self, config: Arc<ServerConfig>) -> Self {
+    self.tls = Some(config);
+    self
+  }
+
+  async fn run(self) -> io::Result<()> {
+    let listener = TcpListener::bind(self.addr).await?;
+    let graceful = GracefulShutdown::new();
+    let mut shutdown = self.shutdown.unwrap_or_else(|| Box::pin(future::pending()));
+
+    let mut builder = http1::Builder::new();
+
+    builder
+      .timer(TokioTimer::new())
+      .header_read_timeout(HEADER_READ_TIMEOUT);
+
+    #[cfg(feature = "server-rustls")]
+    let acceptor = self.tls.map(TlsAcceptor::from);
+
+    loop {
+      let stream = tokio::select! {
+        accepted = listener.accept() => match accepted {
+          Ok((stream, _)) => stream,
+          Err(_) => {
+            tokio::time::sleep(ACCEPT_BACKOFF).await;
+            continue;
+          }
+        },
+
+        () = &mut shutdown => break,
+      };
+
+      let service = TowerToHyperService::new(self.service.clone());
+      let watcher = graceful.watcher();
+      let builder = builder.clone();
+
+      #[cfg(feature = "server-rustls")]
+      let acceptor = acceptor.clone();
+
+      spawn(async move {
+        #[cfg(feature = "server-rustls")]
+        if let Some(acceptor) = acceptor {
+          // hyper's header read timeout only starts once the handshake is done.
+          if let Ok(Ok(stream)) =
+            tokio::time::timeout(HEADER_READ_TIMEOUT, acceptor.accept(stream)).await
+          {
+            let _ = watcher
+              .watch(builder.serve_connection(TokioIo::new(stream), service))
+              .await;
+          }
+
+          return;
+        }
+
+        let _ = watcher
+          .watch(builder.serve_connection(TokioIo::new(stream), service))
+          .await;
+      });
+    }
+
+    drop(listener);
+    graceful.shutdown().await;
+
+    Ok(())
+  }
+}
+
+impl<S> IntoFuture for Serve<S>
+where
+  S: Service<Request<Incoming>, Response = Response<Full<Bytes>>, Error = Infallible>
+    + Clone
+    + Send
+    + 'static,
+  S::Future: Send,
+{
+  type Output = io::Result<()>;
+  type IntoFuture = Pin<Box<dyn Future<Output = Self::Output> + Send>>;
+
+  #[inline(always)]
+  fn into_future(self) -> Self::IntoFuture {
+    Box::pin(self.run())
+  }
+}
+
+/// Creates a standalone vote webhook server listening on the specified address, for bots without a web framework. Every POST request to any path is handled as a vote. (See [`VoteService`])
+///
+/// # Errors
+///
+/// The returned [`Serve`] errors once `.await`ed if the server cannot bind to the specified address.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust,no_run
+/// use std::sync::Arc;
+/// use topgg::{Vote, VoteHandler};
+///
+/// struct MyVoteHandler {}
+///
+/// #[async_trait::async_trait]
+/// impl VoteHandler for MyVoteHandler {
+///   async fn voted(&self, vote: Vote) {
+///     println!("{:?}", vote);
+///   }
+/// }
+///
+/// # async fn run(ctrl_c: impl core::future::Future<Output = ()> + Send + 'static) -> std::io::Result<()> {
+/// topgg::server::serve(([0, 0, 0, 0], 8080), "my-webhook-password", Arc::new(MyVoteHandler {}))
+///   .with_graceful_shutdown(ctrl_c)
+///   .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[inline(always)]
+pub fn serve<A, P, T>(addr: A, passwords: P, handler: Arc<T>) -> Serve<VoteService<T>>
+where
+  A: Into<SocketAddr>,
+  P: Into<Passwords>,
+  T: FallibleVoteHandler,
+{
+  Serve::new(addr.into(), VoteService::new(passwords, handler))
+}
+
+/// Creates a standalone webhook server for signed [Top.gg](https://top.gg) webhook deliveries listening on the specified address, for bots without a web framework. Every POST request to any path is handled as an event. (See [`EventService`])
+///
+/// # Errors
+///
+/// The returned [`Serve`] errors once `.await`ed if the server cannot bind to the specified address.
+#[inline(always)]
+pub fn serve_events<A, S, T>(addr: A, secret: S, handler: Arc<T>) -> Serve<EventService<T>>
+where
+  A: Into<SocketAddr>,
+  S: Into<WebhookSecret>,
+  T: WebhookEventHandler,
+{
+  Serve::new(addr.into(), EventService::new(secret, handler))
+}
@@ -0,0 +1,326 @@
+use super::auth::constant_time_eq;
+use crate::{WebhookError, WebhookEvent};
+use core::time::Duration;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The name of the header containing a signed [Top.gg](https://top.gg) webhook delivery's signature.
+pub const SIGNATURE_HEADER: &str = "x-topgg-signature";
+
+const DEFAULT_TOLERANCE: Duration = Duration::from_secs(300);
+
+fn decode_hex(input: &str) -> Option<Vec<u8>> {
+  input
+    .as_bytes()
+    .chunks(2)
+    .map(|pair| match pair {
+      [high, low] => {
+        Some(((*high as char).to_digit(16)? << 4 | (*low as char).to_digit(16)?) as u8)
+      }
+      _ => None,
+    })
+    .collect()
+}
+
+/// One or more accepted secrets for verifying signed [Top.gg](https://top.gg) webhook deliveries, with a replay window for their timestamps.
+///
+/// Accepting several secrets at once allows rotating a webhook secret without rejecting deliveries during the switch.
+///
+/// Signed deliveries carry a `x-topgg-signature: t={timestamp},v1={signature}` header, where the signature is a hex-encoded HMAC-SHA256 of `{timestamp}.{body}` keyed with this secret.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust,no_run
+/// use core::time::Duration;
+/// use topgg::{WebhookEvent, WebhookSecret};
+///
+/// # fn run(signature: &str, body: &[u8]) {
+/// let secret = WebhookSecret::new("my-webhook-secret").tolerance(Duration::from_secs(60));
+///
+/// match secret.verify(signature, body) {
+///   Ok(WebhookEvent::VoteCreate(vote)) => println!("{} voted!", vote.user.name),
+///   Ok(_) => {}
+///   Err(err) => println!("rejected delivery: {err}"),
+/// }
+/// # }
+/// ```
+#[must_use]
+#[derive(Clone)]
+pub struct WebhookSecret {
+  secrets: Vec<String>,
+  tolerance: Duration,
+}
+
+impl WebhookSecret {
+  /// Creates a brand new [`WebhookSecret`] that accepts a single secret, with a replay window of 5 minutes.
+  #[inline(always)]
+  pub fn new<S>(secret: S) -> Self
+  where
+    S: Into<String>,
+  {
+    Self {
+      secrets: vec![secret.into()],
+      tolerance: DEFAULT_TOLERANCE,
+    }
+  }
+
+  /// Accepts another secret.
+  #[inline(always)]
+  pub fn and<S>(mut self, secret: S) -> Self
+  where
+    S: Into<String>,
+  {
+    self.secrets.push(secret.into());
+    self
+  }
+
+  /// Sets the maximum difference between a delivery's timestamp and the current time before it gets rejected as a replay.
+  #[inline(always)]
+  pub fn tolerance(mut self, tolerance: Duration) -> Self {
+    self.tolerance = tolerance;
+    self
+  }
+
+  /// Verifies the signature and timestamp of a delivery without parsing its body.
+  ///
+  /// # Errors
+  ///
+  /// Errors if any of the following conditions are met:
+  /// - The signature header is not in the `t={timestamp},v1={signature}` format ([`MalformedSignature`][WebhookError::MalformedSignature])
+  /// - The timestamp is outside of the replay window ([`Expired`][WebhookError::Expired])
+  /// - None of the signatures match any of the accepted secrets ([`InvalidSignature`][WebhookError::InvalidSignature])
+  pub fn verify_signature(&self, signature: &str, body: &[u8]) -> Result<(), WebhookError> {
+    let mut timestamp = None;
+    let mut signatures = Vec::new();
+
+    for part in signature.split(',') {
+      match part.trim().split_once('=') {
+        Some(("t", value)) => {
+          timestamp = Some(
+            value
+              .parse::<u64>()
+              .map_err(|_| WebhookError::MalformedSignature)?,
+          )
+        }
+
+        Some(("v1", value)) => {
+          signatures.push(decode_hex(value).ok_or(WebhookError::MalformedSignature)?)
+        }
+
+        _ => {}
+      }
+    }
+
+    let timestamp = timestamp.ok_or(WebhookError::MalformedSignature)?;
+
+    if signatures.is_empty() {
+      return Err(WebhookError::MalformedSignature);
+    }
+
+    let now = SystemTime::now()
+      .duration_since(UNIX_EPOCH)
+      .unwrap_or_default()
+      .as_secs();
+
+    if now.abs_diff(timestamp) > self.tolerance.as_secs() {
+      return Err(WebhookError::Expired);
+    }
+
+    let mut matched = false;
+
+    for secret in &self.secrets {
+      let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).unwrap();
+
+      mac.update(timestamp.to_string().as_bytes());
+      mac.update(b".");
+      mac.update(body);
+
+      let expected = mac.finalize().into_bytes();
+
+      for signature in &signatures {
+        matched |= constant_time_eq(&expected, signature);
+      }
+    }
+
+    if matched {
+      Ok(())
+    } else {
+      Err(WebhookError::InvalidSignature)
+    }
+  }
+
+  /// Verifies the signature and timestamp of a delivery, then parses its body into a [`WebhookEvent`].
+  ///
+  /// # Errors
+  ///
+  /// Errors if any of the following conditions are met:
+  /// - The signature header is not in the `t={timestamp},v1={signature}` format ([`MalformedSignature`][WebhookError::MalformedSignature])
+  /// - The timestamp is outside of the replay window ([`Expired`][WebhookError::Expired])
+  /// - None of the signatures match any of the accepted secrets ([`InvalidSignature`][WebhookError::InvalidSignature])
+  /// - The body cannot be deserialized ([`Payload`][WebhookError::Payload])
+  pub fn verify(&self, signature: &str, body: &[u8]) -> Result<WebhookEvent, WebhookError> {
+    self.verify_signature(signature, body)?;
+
+    serde_json::from_slice(body).map_err(WebhookError::Payload)
+  }
+}
+
+impl From<String> for WebhookSecret {
+  #[inline(always)]
+  fn from(secret: String) -> Self {
+    Self::new(secret)
+  }
+}
+
+impl From<&str> for WebhookSecret {
+  #[inline(always)]
+  fn from(secret: &str) -> Self {
+    Self::new(secret)
+  }
+}
+
+impl From<Vec<String>> for WebhookSecret {
+  #[inline(always)]
+  fn from(secrets: Vec<String>) -> Self {
+    Self {
+      secrets,
+      tolerance: DEFAULT_TOLERANCE,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const BODY: &[u8] = br#"{"hello":"world"}"#;
+
+  fn now() -> u64 {
+    SystemTime::now()
+      .duration_since(UNIX_EPOCH)
+      .unwrap()
+      .as_secs()
+  }
+
+  fn sign(secret: &str, timestamp: u64) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).unwrap();
+
+    mac.update(format!("{timestamp}.").as_bytes());
+    mac.update(BODY);
+
+    mac
+      .finalize()
+      .into_bytes()
+      .iter()
+      .map(|byte| format!("{byte:02x}"))
+      .collect()
+  }
+
+  #[test]
+  fn accepts_known_signature() {
+    let secret = WebhookSecret::new("my-webhook-secret").tolerance(Duration::MAX);
+
+    assert!(secret
+      .verify_signature(
+        "t=1700000000,v1=2ebac2dfaaebfbd94a03a2980087637b7c2a20129e7ca7a955e0ccded753a16b",
+        BODY
+      )
+      .is_ok());
+
+    assert!(matches!(
+      secret.verify_signature(
+        "t=1700000001,v1=2ebac2dfaaebfbd94a03a2980087637b7c2a20129e7ca7a955e0ccded753a16b",
+        BODY
+      ),
+      Err(WebhookError::InvalidSignature)
+    ));
+  }
+
+  #[test]
+  fn rejects_wrong_secret() {
+    let now = now();
+    let signature = format!("t={now},v1={}", sign("another-secret", now));
+
+    assert!(matches!(
+      WebhookSecret::new("my-webhook-secret").verify_signature(&signature, BODY),
+      Err(WebhookError::InvalidSignature)
+    ));
+  }
+
+  #[test]
+  fn rejects_expired_timestamp() {
+    let secret = WebhookSecret::new("my-webhook-secret").tolerance(Duration::from_secs(60));
+
+    for timestamp in [now() - 120, now() + 120] {
+      let signature = format!("t={timestamp},v1={}", sign("my-webhook-secret", timestamp));
+
+      assert!(matches!(
+        secret.verify_signature(&signature, BODY),
+        Err(WebhookError::Expired)
+      ));
+    }
+
+    let timestamp = now() - 30;
+    let signature = format!("t={timestamp},v1={}", sign("my-webhook-secret", timestamp));
+
+    assert!(secret.verify_signature(&signature, BODY).is_ok());
+  }
+
+  #[test]
+  fn rejects_malformed_header() {
+    let now = now();
+    let signature = sign("my-webhook-secret", now);
+    let secret = WebhookSecret::new("my-webhook-secret");
+
+    for header in [
+      format!("v1={signature}"),
+      format!("t={now}"),
+      format!("t=yesterday,v1={signature}"),
+      format!("t={now},v1=not-hex"),
+      format!("t={now},v1={}", &signature[1..]),
+      String::new(),
+    ] {
+      assert!(
+        matches!(
+          secret.verify_signature(&header, BODY),
+          Err(WebhookError::MalformedSignature)
+        ),
+        "{header}"
+      );
+    }
+  }
+
+  #[test]
+  fn accepts_any_signature_and_secret() {
+    let now = now();
+    let old = sign("old-secret", now);
+    let new = sign("new-secret", now);
+
+    let rotating = WebhookSecret::new("old-secret").and("new-secret");
+
+    for signature in [
+      format!("t={now},v1={old}"),
+      format!("t={now},v1={new}"),
+      format!("t={now},v1={}, v1={new}", sign("unknown", now)),
+    ] {
+      assert!(
+        rotating.verify_signature(&signature, BODY).is_ok(),
+        "{signature}"
+      );
+    }
+
+    let signature = format!("t={now},v1={old},v1={new}");
+
+    assert!(WebhookSecret::new("new-secret")
+      .verify_signature(&signature, BODY)
+      .is_ok());
+
+    assert!(matches!(
+      WebhookSecret::new("another-secret").verify_signature(&signature, BODY),
+      Err(WebhookError::InvalidSignature)
+    ));
+  }
+}
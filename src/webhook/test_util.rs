@@ -0,0 +1,44 @@
+use crate::{FallibleVoteHandler, Vote};
+use std::{
+  io,
+  sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+};
+
+/// A handler that counts the votes it receives.
+#[derive(Default)]
+pub(crate) struct Counter {
+  calls: AtomicUsize,
+  fail: AtomicBool,
+}
+
+impl Counter {
+  /// The amount of votes received so far, including failed ones.
+  pub(crate) fn calls(&self) -> usize {
+    self.calls.load(Ordering::SeqCst)
+  }
+
+  /// Makes the next vote fail.
+  pub(crate) fn fail_next(&self) {
+    self.fail.store(true, Ordering::SeqCst);
+  }
+}
+
+#[async_trait::async_trait]
+impl FallibleVoteHandler for Counter {
+  type Error = io::Error;
+
+  async fn try_voted(&self, _vote: Vote) -> Result<(), io::Error> {
+    self.calls.fetch_add(1, Ordering::SeqCst);
+
+    if self.fail.swap(false, Ordering::SeqCst) {
+      Err(io::Error::other("failed"))
+    } else {
+      Ok(())
+    }
+  }
+}
+
+/// A vote from user `2` for bot `1`, where `kind` is either `upvote` or `test`.
+pub(crate) fn vote(kind: &str) -> Vote {
+  serde_json::from_str(&format!(r#"{{"bot":"1","user":"2","type":"{kind}"}}"#)).unwrap()
+}
@@ -0,0 +1,218 @@
+use crate::{FallibleVoteHandler, Passwords, WebhookEventHandler, WebhookSecret};
+use bytes::Bytes;
+use core::{
+  convert::Infallible,
+  future::Future,
+  pin::Pin,
+  task::{Context, Poll},
+};
+use http::{Method, Request, Response, StatusCode};
+use http_body::Body;
+use http_body_util::{BodyExt, Full, LengthLimitError, Limited};
+use std::{error, sync::Arc};
+use tower_service::Service;
+
+/// The maximum size of a request body, which is more than enough for any webhook delivery.
+const MAX_BODY_SIZE: usize = 64 * 1024;
+
+type ResponseFuture =
+  Pin<Box<dyn Future<Output = Result<Response<Full<Bytes>>, Infallible>> + Send>>;
+
+#[inline(always)]
+fn respond(status: StatusCode) -> Response<Full<Bytes>> {
+  let mut response = Response::new(Full::default());
+
+  *response.status_mut() = status;
+  response
+}
+
+/// Buffers the body of a POST request up to [`MAX_BODY_SIZE`], or returns the response to send back right away.
+async fn read_body<B>(request: Request<B>) -> Result<(http::request::Parts, Bytes), StatusCode>
+where
+  B: Body,
+  B::Error: Into<Box<dyn error::Error + Send + Sync>>,
+{
+  if request.method() != Method::POST {
+    return Err(StatusCode::METHOD_NOT_ALLOWED);
+  }
+
+  let (parts, body) = request.into_parts();
+
+  match Limited::new(body, MAX_BODY_SIZE).collect().await {
+    Ok(body) => Ok((parts, body.to_bytes())),
+    Err(err) if err.is::<LengthLimitError>() => Err(StatusCode::PAYLOAD_TOO_LARGE),
+    Err(_) => Err(StatusCode::BAD_REQUEST),
+  }
+}
+
+/// A [`tower`](https://crates.io/crates/tower) [`Service`] for adding an on-vote event handler to your application logic.
+///
+/// It accepts POST requests with a body of up to 64 KiB, authenticates them in constant time and responds with a 200 (OK) HTTP status code after running the [`VoteHandler`][crate::VoteHandler], which can also be a [`FallibleVoteHandler`]. Since it's a plain [`Service`], it can be mounted in any [`tower`](https://crates.io/crates/tower)-compatible stack, e.g. [`hyper`](https://crates.io/crates/hyper) or [`tonic`](https://crates.io/crates/tonic), and wrapped with layers such as timeouts or concurrency limits.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust,no_run
+/// use std::sync::Arc;
+/// use topgg::{tower::VoteService, Vote, VoteHandler};
+///
+/// struct MyVoteHandler {}
+///
+/// #[async_trait::async_trait]
+/// impl VoteHandler for MyVoteHandler {
+///   async fn voted(&self, vote: Vote) {
+///     println!("{:?}", vote);
+///   }
+/// }
+///
+/// let service = VoteService::new("my-webhook-password", Arc::new(MyVoteHandler {}));
+/// ```
+#[must_use]
+pub struct VoteService<T> {
+  passwords: Arc<Passwords>,
+  handler: Arc<T>,
+}
+
+impl<T> VoteService<T>
+where
+  T: FallibleVoteHandler,
+{
+  /// Creates a brand new [`VoteService`] that accepts the specified password(s). (See [`Passwords`])
+  #[inline(always)]
+  pub fn new<P>(passwords: P, handler: Arc<T>) -> Self
+  where
+    P: Into<Passwords>,
+  {
+    Self {
+      passwords: Arc::new(passwords.into()),
+      handler,
+    }
+  }
+}
+
+impl<T> Clone for VoteService<T> {
+  #[inline(always)]
+  fn clone(&self) -> Self {
+    Self {
+      passwords: Arc::clone(&self.passwords),
+      handler: Arc::clone(&self.handler),
+    }
+  }
+}
+
+impl<T, B> Service<Request<B>> for VoteService<T>
+where
+  T: FallibleVoteHandler,
+  B: Body + Send + 'static,
+  B::Data: Send,
+  B::Error: Into<Box<dyn error::Error + Send + Sync>>,
+{
+  type Response = Response<Full<Bytes>>;
+  type Error = Infallible;
+  type Future = ResponseFuture;
+
+  #[inline(always)]
+  fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+    Poll::Ready(Ok(()))
+  }
+
+  fn call(&mut self, request: Request<B>) -> Self::Future {
+    let passwords = Arc::clone(&self.passwords);
+    let handler = Arc::clone(&self.handler);
+
+    Box::pin(async move {
+      let (parts, body) = match read_body(request).await {
+        Ok(request) => request,
+        Err(status) => return Ok(respond(status)),
+      };
+
+      let result = match crate::verify_and_parse(&passwords, &parts.headers, &body) {
+        Ok(vote) => crate::handle_vote(&handler, vote).await,
+        Err(err) => Err(err),
+      };
+
+      Ok(match result {
+        Ok(()) => respond(StatusCode::OK),
+        Err(err) => respond(StatusCode::from_u16(err.status_code()).unwrap()),
+      })
+    })
+  }
+}
+
+/// A [`tower`](https://crates.io/crates/tower) [`Service`] for adding an on-event handler for signed [Top.gg](https://top.gg) webhook deliveries to your application logic.
+///
+/// It accepts POST requests with a body of up to 64 KiB, verifies their signature and timestamp and responds with a 200 (OK) HTTP status code after running the [`WebhookEventHandler`]. (See [`VoteService`])
+#[must_use]
+pub struct EventService<T> {
+  secret: Arc<WebhookSecret>,
+  handler: Arc<T>,
+}
+
+impl<T> EventService<T>
+where
+  T: WebhookEventHandler,
+{
+  /// Creates a brand new [`EventService`] that accepts the specified secret(s). (See [`WebhookSecret`])
+  #[inline(always)]
+  pub fn new<S>(secret: S, handler: Arc<T>) -> Self
+  where
+    S: Into<WebhookSecret>,
+  {
+    Self {
+      secret: Arc::new(secret.into()),
+      handler,
+    }
+  }
+}
+
+impl<T> Clone for EventService<T> {
+  #[inline(always)]
+  fn clone(&self) -> Self {
+    Self {
+      secret: Arc::clone(&self.secret),
+      handler: Arc::clone(&self.handler),
+    }
+  }
+}
+
+impl<T, B> Service<Request<B>> for EventService<T>
+where
+  T: WebhookEventHandler,
+  B: Body + Send + 'static,
+  B::Data: Send,
+  B::Error: Into<Box<dyn error::Error + Send + Sync>>,
+{
+  type Response = Response<Full<Bytes>>;
+  type Error = Infallible;
+  type Future = ResponseFuture;
+
+  #[inline(always)]
+  fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+    Poll::Ready(Ok(()))
+  }
+
+  fn call(&mut self, request: Request<B>) -> Self::Future {
+    let secret = Arc::clone(&self.secret);
+    let handler = Arc::clone(&self.handler);
+
+    Box::pin(async move {
+      let (parts, body) = match read_body(request).await {
+        Ok(request) => request,
+        Err(status) => return Ok(respond(status)),
+      };
+
+      Ok(
+        match crate::verify_and_parse_event(&secret, &parts.headers, &body) {
+          Ok(event) => {
+            handler.handle(event).await;
+
+            respond(StatusCode::OK)
+          }
+
+          Err(err) => respond(StatusCode::from_u16(err.status_code()).unwrap()),
+        },
+      )
+    })
+  }
+}
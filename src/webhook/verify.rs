@@ -0,0 +1,183 @@
+use crate::{
+  FallibleVoteHandler, Passwords, Vote, WebhookError, WebhookEvent, WebhookSecret, SIGNATURE_HEADER,
+};
+use http::{header::AUTHORIZATION, HeaderMap};
+use std::{error, sync::Arc};
+
+/// Authenticates a legacy vote webhook request's `Authorization` header value, then parses its body. Every framework wrapper delegates to this.
+pub(crate) fn parse_vote(
+  passwords: &Passwords,
+  authorization: Option<&str>,
+  body: &[u8],
+) -> Result<Vote, WebhookError> {
+  let authorization = authorization.ok_or(WebhookError::MissingAuthorization)?;
+
+  if !passwords.verify(authorization) {
+    return Err(WebhookError::Unauthorized);
+  }
+
+  serde_json::from_slice(body).map_err(WebhookError::Payload)
+}
+
+/// Runs a [`FallibleVoteHandler`] (or a [`VoteHandler`][crate::VoteHandler]) on a vote, applying its [`timeout`][FallibleVoteHandler::timeout] if any.
+///
+/// This is what every framework wrapper in this SDK runs after [`verify_and_parse`], so the resulting [`status_code`][WebhookError::status_code] can be used to respond the same way they do. A handler that times out is left running in the background instead of being cancelled, so that wrappers like [`Dedup`][crate::Dedup] and [`Journal`][crate::Journal] aren't interrupted halfway.
+///
+/// # Errors
+///
+/// Errors if any of the following conditions are met:
+/// - The handler fails or panics ([`Handler`][WebhookError::Handler])
+/// - The handler doesn't finish within its timeout ([`Timeout`][WebhookError::Timeout])
+///
+/// If the handler fails with a [`WebhookError`] itself, it's returned as is.
+///
+/// # Panics
+///
+/// Panics if the handler has a timeout and this is not called within a [`tokio`] runtime.
+pub async fn handle_vote<T>(handler: &Arc<T>, vote: Vote) -> Result<(), WebhookError>
+where
+  T: FallibleVoteHandler,
+{
+  match handler.timeout() {
+    Some(timeout) => {
+      let handler = Arc::clone(handler);
+      let task = tokio::spawn(async move { handler.try_voted(vote).await });
+
+      match tokio::time::timeout(timeout, task).await {
+        Ok(Ok(result)) => result.map_err(handler_error),
+        Ok(Err(err)) => Err(WebhookError::Handler(Box::new(err))),
+        Err(_) => Err(WebhookError::Timeout),
+      }
+    }
+
+    None => handler.try_voted(vote).await.map_err(handler_error),
+  }
+}
+
+/// Wraps a handler's error, unless it's already a [`WebhookError`] (e.g. from a [`VoteRouter`][crate::VoteRouter]).
+fn handler_error<E>(err: E) -> WebhookError
+where
+  E: error::Error + Send + Sync + 'static,
+{
+  let err: Box<dyn error::Error + Send + Sync> = Box::new(err);
+
+  match err.downcast() {
+    Ok(err) => *err,
+    Err(err) => WebhookError::Handler(err),
+  }
+}
+
+/// Verifies a signed webhook request's `x-topgg-signature` header value, then parses its body. Every framework wrapper delegates to this.
+#[inline(always)]
+pub(crate) fn parse_event(
+  secret: &WebhookSecret,
+  signature: Option<&str>,
+  body: &[u8],
+) -> Result<WebhookEvent, WebhookError> {
+  secret.verify(signature.ok_or(WebhookError::MissingSignature)?, body)
+}
+
+/// Authenticates a [Top.gg](https://top.gg) vote webhook request from its [`http`] headers and raw body, then parses it into a [`Vote`].
+///
+/// This is what every framework wrapper in this SDK uses under the hood, so it can be used to support web frameworks that aren't supported by this SDK.
+///
+/// # Errors
+///
+/// Errors if any of the following conditions are met:
+/// - The request has no valid `Authorization` header ([`MissingAuthorization`][WebhookError::MissingAuthorization])
+/// - The `Authorization` header doesn't match any of the accepted passwords ([`Unauthorized`][WebhookError::Unauthorized])
+/// - The body cannot be deserialized ([`Payload`][WebhookError::Payload])
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust,no_run
+/// use http::{Request, StatusCode};
+/// use topgg::Passwords;
+///
+/// fn on_request(request: Request<Vec<u8>>) -> StatusCode {
+///   let passwords = Passwords::new("my-webhook-password");
+///
+///   match topgg::verify_and_parse(&passwords, request.headers(), request.body()) {
+///     Ok(vote) => {
+///       println!("{:?}", vote);
+///
+///       StatusCode::OK
+///     }
+///
+///     Err(err) => StatusCode::from_u16(err.status_code()).unwrap(),
+///   }
+/// }
+/// ```
+#[inline(always)]
+pub fn verify_and_parse(
+  passwords: &Passwords,
+  headers: &HeaderMap,
+  body: &[u8],
+) -> Result<Vote, WebhookError> {
+  parse_vote(
+    passwords,
+    headers
+      .get(AUTHORIZATION)
+      .and_then(|authorization| authorization.to_str().ok()),
+    body,
+  )
+}
+
+/// Verifies a signed [Top.gg](https://top.gg) webhook request from its [`http`] headers and raw body, then parses it into a [`WebhookEvent`].
+///
+/// This is what every framework wrapper in this SDK uses under the hood, so it can be used to support web frameworks that aren't supported by this SDK.
+///
+/// # Errors
+///
+/// Errors if any of the following conditions are met:
+/// - The request has no valid `x-topgg-signature` header ([`MissingSignature`][WebhookError::MissingSignature])
+/// - The signature header is not in the `t={timestamp},v1={signature}` format ([`MalformedSignature`][WebhookError::MalformedSignature])
+/// - The timestamp is outside of the replay window ([`Expired`][WebhookError::Expired])
+/// - None of the signatures match any of the accepted secrets ([`InvalidSignature`][WebhookError::InvalidSignature])
+/// - The body cannot be deserialized ([`Payload`][WebhookError::Payload])
+#[inline(always)]
+pub fn verify_and_parse_event(
+  secret: &WebhookSecret,
+  headers: &HeaderMap,
+  body: &[u8],
+) -> Result<WebhookEvent, WebhookError> {
+  parse_event(
+    secret,
+    headers
+      .get(SIGNATURE_HEADER)
+      .and_then(|signature| signature.to_str().ok()),
+    body,
+  )
+}
+
+cfg_if::cfg_if! {
+  if #[cfg(any(feature = "actix-web", feature = "axum", feature = "rocket"))] {
+    /// A request that the actix-web and rocket extractors buffer as-is, to be authenticated later.
+    pub(crate) trait Incoming {
+      /// The header containing this request's credentials.
+      const HEADER: &'static str;
+
+      fn new(credentials: Option<String>, body: Vec<u8>) -> Self;
+    }
+
+    impl Incoming for crate::IncomingVote {
+      const HEADER: &'static str = "Authorization";
+
+      #[inline(always)]
+      fn new(authorization: Option<String>, body: Vec<u8>) -> Self {
+        Self { authorization, body }
+      }
+    }
+
+    impl Incoming for crate::IncomingEvent {
+      const HEADER: &'static str = SIGNATURE_HEADER;
+
+      #[inline(always)]
+      fn new(signature: Option<String>, body: Vec<u8>) -> Self {
+        Self { signature, body }
+      }
+    }
+  }
+}
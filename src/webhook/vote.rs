@@ -1,6 +1,9 @@
 use crate::snowflake;
-use serde::{Deserialize, Deserializer};
-use std::collections::HashMap;
+use core::{convert::Infallible, time::Duration};
+use serde::{
+  de::DeserializeOwned, ser::SerializeStruct, Deserialize, Deserializer, Serialize, Serializer,
+};
+use std::{collections::HashMap, error};
 
 #[inline(always)]
 fn deserialize_is_test<'de, D>(deserializer: D) -> Result<bool, D::Error>
@@ -10,94 +13,278 @@ where
   String::deserialize(deserializer).map(|s| s == "test")
 }
 
-const fn _true() -> bool {
-  true
-}
-
 #[inline(always)]
-fn deserialize_is_server<'de, D>(deserializer: D) -> Result<bool, D::Error>
+fn deserialize_optional_id<'de, D>(deserializer: D) -> Result<Option<u64>, D::Error>
 where
   D: Deserializer<'de>,
 {
-  Ok(String::deserialize(deserializer).is_err())
+  snowflake::deserialize(deserializer).map(Some)
 }
 
-fn deserialize_query_string<'de, D>(deserializer: D) -> Result<HashMap<String, String>, D::Error>
+#[inline(always)]
+fn deserialize_query_string<'de, D>(deserializer: D) -> Result<String, D::Error>
 where
   D: Deserializer<'de>,
 {
   Ok(
     String::deserialize(deserializer)
-      .map(|s| {
-        let mut output = HashMap::new();
-
-        for mut it in s.split('&').map(|pair| pair.split('=')) {
-          if let (Some(k), Some(v)) = (it.next(), it.next()) {
-            if let Ok(v) = urlencoding::decode(v) {
-              output.insert(k.to_owned(), v.into_owned());
-            }
-          }
-        }
-
-        output
+      .map(|s| match s.strip_prefix('?') {
+        Some(stripped) => stripped.to_owned(),
+        None => s,
       })
       .unwrap_or_default(),
   )
 }
 
+#[derive(Deserialize)]
+struct RawVote {
+  #[serde(default, deserialize_with = "deserialize_optional_id")]
+  bot: Option<u64>,
+
+  #[serde(default, deserialize_with = "deserialize_optional_id")]
+  guild: Option<u64>,
+
+  #[serde(deserialize_with = "snowflake::deserialize", rename = "user")]
+  voter_id: u64,
+
+  #[serde(deserialize_with = "deserialize_is_test", rename = "type")]
+  is_test: bool,
+
+  #[serde(default, rename = "isWeekend")]
+  is_weekend: bool,
+
+  #[serde(default, deserialize_with = "deserialize_query_string")]
+  query: String,
+}
+
+impl TryFrom<RawVote> for Vote {
+  type Error = &'static str;
+
+  fn try_from(raw: RawVote) -> Result<Self, Self::Error> {
+    let target = match (raw.bot, raw.guild) {
+      (_, Some(id)) => VoteTarget::Server(id),
+      (Some(id), None) => VoteTarget::Bot(id),
+      (None, None) => return Err("missing field `bot` or `guild`"),
+    };
+
+    Ok(Self {
+      target,
+      voter_id: raw.voter_id,
+      is_test: raw.is_test,
+      is_weekend: raw.is_weekend,
+      query: serde_urlencoded::from_str::<Vec<(String, String)>>(&raw.query)
+        .unwrap_or_default()
+        .into_iter()
+        .collect(),
+      query_string: raw.query,
+    })
+  }
+}
+
+/// The Discord bot or server that received a [`Vote`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum VoteTarget {
+  /// A Discord bot, with its ID.
+  Bot(u64),
+
+  /// A Discord server, with its ID.
+  Server(u64),
+}
+
+impl VoteTarget {
+  /// The ID of the Discord bot or server that received the vote.
+  #[must_use]
+  #[inline(always)]
+  pub const fn id(&self) -> u64 {
+    match self {
+      Self::Bot(id) | Self::Server(id) => *id,
+    }
+  }
+
+  /// Whether the vote was received by a Discord bot.
+  #[must_use]
+  #[inline(always)]
+  pub const fn is_bot(&self) -> bool {
+    matches!(self, Self::Bot(_))
+  }
+
+  /// Whether the vote was received by a Discord server.
+  #[must_use]
+  #[inline(always)]
+  pub const fn is_server(&self) -> bool {
+    matches!(self, Self::Server(_))
+  }
+}
+
 /// A struct representing a dispatched [Top.gg](https://top.gg) bot/server vote event.
 #[must_use]
 #[derive(Clone, Debug, Deserialize)]
+#[serde(try_from = "RawVote")]
 pub struct Vote {
-  /// The ID of the bot/server that received a vote.
-  #[serde(
-    deserialize_with = "snowflake::deserialize",
-    alias = "bot",
-    alias = "guild"
-  )]
-  pub receiver_id: u64,
+  /// The Discord bot or server that received this vote.
+  pub target: VoteTarget,
 
   /// The ID of the user who voted.
-  #[serde(deserialize_with = "snowflake::deserialize", rename = "user")]
   pub voter_id: u64,
 
-  /// Whether this vote's receiver is a server or not (bot otherwise).
-  #[serde(
-    default = "_true",
-    deserialize_with = "deserialize_is_server",
-    rename = "bot"
-  )]
-  pub is_server: bool,
-
   /// Whether this vote is just a test coming from the bot/server owner or not. Most of the time this would be `false`.
-  #[serde(deserialize_with = "deserialize_is_test", rename = "type")]
   pub is_test: bool,
 
   /// Whether the weekend multiplier is active or not, meaning a single vote counts as two.
   /// If the dispatched event came from a server being voted, this will always be `false`.
-  #[serde(default, rename = "isWeekend")]
   pub is_weekend: bool,
 
-  /// query strings found on the vote page.
-  #[serde(default, deserialize_with = "deserialize_query_string")]
+  /// query strings found on the vote page. If a key is repeated, only its last value is kept. (See [`query_multimap`][Vote::query_multimap])
   pub query: HashMap<String, String>,
+
+  query_string: String,
+}
+
+impl Vote {
+  /// The raw query string found on the vote page, without the leading `?`.
+  #[must_use]
+  #[inline(always)]
+  pub fn query_string(&self) -> &str {
+    &self.query_string
+  }
+
+  /// Deserializes the query strings found on the vote page into any type, following the `application/x-www-form-urlencoded` format. (See [`vote_url_with_query`])
+  ///
+  /// # Errors
+  ///
+  /// Errors if the query strings cannot be deserialized into the specified type, e.g. if a required key is missing.
+  ///
+  /// # Examples
+  ///
+  /// Basic usage:
+  ///
+  /// ```rust,no_run
+  /// use serde::Deserialize;
+  /// use topgg::Vote;
+  ///
+  /// #[derive(Deserialize)]
+  /// struct Referral {
+  ///   code: String,
+  /// }
+  ///
+  /// # fn run(vote: Vote) {
+  /// if let Ok(referral) = vote.query_as::<Referral>() {
+  ///   println!("referred with {}", referral.code);
+  /// }
+  /// # }
+  /// ```
+  #[inline(always)]
+  pub fn query_as<T>(&self) -> Result<T, serde_urlencoded::de::Error>
+  where
+    T: DeserializeOwned,
+  {
+    serde_urlencoded::from_str(&self.query_string)
+  }
+
+  /// Every value of every query string found on the vote page, in the order they appear.
+  #[must_use]
+  pub fn query_multimap(&self) -> HashMap<String, Vec<String>> {
+    let mut output: HashMap<String, Vec<String>> = HashMap::new();
+
+    for (k, v) in
+      serde_urlencoded::from_str::<Vec<(String, String)>>(&self.query_string).unwrap_or_default()
+    {
+      output.entry(k).or_default().push(v);
+    }
+
+    output
+  }
+}
+
+/// Serializes this vote back into the same JSON format that [Top.gg](https://top.gg) delivers it in.
+impl Serialize for Vote {
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: Serializer,
+  {
+    let mut state = serializer.serialize_struct("Vote", 5)?;
+
+    match self.target {
+      VoteTarget::Bot(id) => state.serialize_field("bot", &id.to_string())?,
+      VoteTarget::Server(id) => state.serialize_field("guild", &id.to_string())?,
+    }
+
+    state.serialize_field("user", &self.voter_id.to_string())?;
+    state.serialize_field("type", if self.is_test { "test" } else { "upvote" })?;
+    state.serialize_field("isWeekend", &self.is_weekend)?;
+
+    if self.query_string.is_empty() {
+      state.serialize_field("query", "")?;
+    } else {
+      state.serialize_field("query", &format!("?{}", self.query_string))?;
+    }
+
+    state.end()
+  }
+}
+
+/// Creates the URL of a Discord bot's or server's [Top.gg](https://top.gg) vote page with the specified query strings, which are delivered back with every vote made from it.
+///
+/// The query strings are serialized in the `application/x-www-form-urlencoded` format, so they can be deserialized back with [`Vote::query_as`].
+///
+/// # Errors
+///
+/// Errors if the query strings cannot be serialized, e.g. if they contain nested structures.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust,no_run
+/// # fn run() -> Result<(), Box<dyn std::error::Error>> {
+/// use topgg::{vote_url_with_query, VoteTarget};
+///
+/// let url = vote_url_with_query(VoteTarget::Bot(264811613708746752), &[("code", "summer sale")])?;
+///
+/// assert_eq!(url, "https://top.gg/bot/264811613708746752/vote?code=summer+sale");
+/// # Ok(())
+/// # }
+/// ```
+pub fn vote_url_with_query<Q>(
+  target: VoteTarget,
+  query: &Q,
+) -> Result<String, serde_urlencoded::ser::Error>
+where
+  Q: Serialize + ?Sized,
+{
+  let url = match target {
+    VoteTarget::Bot(id) => format!("https://top.gg/bot/{id}/vote"),
+    VoteTarget::Server(id) => format!("https://top.gg/servers/{id}/vote"),
+  };
+
+  let query = serde_urlencoded::to_string(query)?;
+
+  Ok(if query.is_empty() {
+    url
+  } else {
+    format!("{url}?{query}")
+  })
 }
 
 cfg_if::cfg_if! {
-  if #[cfg(any(feature = "actix-web", feature = "rocket"))] {
+  if #[cfg(any(feature = "actix-web", feature = "axum", feature = "rocket"))] {
+    use crate::{Passwords, WebhookError};
+
     /// A struct that represents an **unauthenticated** request containing a [`Vote`] data.
     ///
     /// To authenticate this structure with a valid password and consume the [`Vote`] data inside of it, see the [`authenticate`][IncomingVote::authenticate] method.
     #[must_use]
-    #[cfg_attr(docsrs, doc(cfg(any(feature = "actix-web", feature = "rocket"))))]
+    #[cfg_attr(docsrs, doc(cfg(any(feature = "actix-web", feature = "axum", feature = "rocket"))))]
     #[derive(Clone)]
     pub struct IncomingVote {
-      pub(crate) authorization: String,
-      pub(crate) vote: Vote,
+      pub(crate) authorization: Option<String>,
+      pub(crate) body: Vec<u8>,
     }
 
     impl IncomingVote {
-      /// Authenticates a valid password with this request. Returns a [`Some(Vote)`][`Vote`] if succeeds, otherwise `None`.
+      /// Authenticates a valid password with this request in constant time. Returns a [`Some(Vote)`][`Vote`] if succeeds, otherwise `None`.
+      ///
+      /// Several passwords can be accepted at once through [`Passwords`].
       ///
       /// # Examples
       ///
@@ -119,33 +306,271 @@ cfg_if::cfg_if! {
       /// ```
       #[must_use]
       #[inline(always)]
-      pub fn authenticate(self, password: &str) -> Option<Vote> {
-        if self.authorization == password {
-          Some(self.vote)
-        } else {
-          None
-        }
+      pub fn authenticate<P>(self, passwords: P) -> Option<Vote>
+      where
+        P: Into<Passwords>,
+      {
+        self.verify(&passwords.into()).ok()
+      }
+
+      /// Authenticates this request like [`authenticate`][IncomingVote::authenticate], but returns why it failed. (See [`verify_and_parse`][crate::verify_and_parse])
+      ///
+      /// # Errors
+      ///
+      /// Errors if any of the following conditions are met:
+      /// - The request has no valid `Authorization` header ([`MissingAuthorization`][WebhookError::MissingAuthorization])
+      /// - The `Authorization` header doesn't match any of the accepted passwords ([`Unauthorized`][WebhookError::Unauthorized])
+      /// - The body cannot be deserialized ([`Payload`][WebhookError::Payload])
+      #[inline(always)]
+      pub fn verify(self, passwords: &Passwords) -> Result<Vote, WebhookError> {
+        super::verify::parse_vote(passwords, self.authorization.as_deref(), &self.body)
       }
     }
   }
 }
 
-cfg_if::cfg_if! {
-  if #[cfg(any(feature = "axum", feature = "warp"))] {
-    /// An async trait for adding an on-vote event handler to your application logic.
-    ///
-    /// It's described as follows (without [`async_trait`]'s macro expansion):
-    /// ```rust,no_run
-    /// #[async_trait::async_trait]
-    /// pub trait VoteHandler: Send + Sync + 'static {
-    ///   async fn voted(&self, vote: Vote);
-    /// }
-    /// ```
-    #[cfg_attr(docsrs, doc(cfg(any(feature = "axum", feature = "warp"))))]
-    #[async_trait::async_trait]
-    pub trait VoteHandler: Send + Sync + 'static {
-      /// Your vote handler's on-vote async callback. The endpoint will always return a 200 (OK) HTTP status code after running this method.
-      async fn voted(&self, vote: Vote);
+/// An async trait for adding an on-vote event handler to your application logic.
+///
+/// It's described as follows (without [`async_trait`]'s macro expansion):
+/// ```rust,no_run
+/// # use core::time::Duration;
+/// # use topgg::Vote;
+/// #[async_trait::async_trait]
+/// pub trait VoteHandler: Send + Sync + 'static {
+///   async fn voted(&self, vote: Vote);
+///
+///   fn timeout(&self) -> Option<Duration> {
+///     None
+///   }
+/// }
+/// ```
+///
+/// Every [`VoteHandler`] is also a [`FallibleVoteHandler`] that never fails.
+#[async_trait::async_trait]
+pub trait VoteHandler: Send + Sync + 'static {
+  /// Your vote handler's on-vote async callback. The endpoint will always return a 200 (OK) HTTP status code after running this method, unless it takes longer than [`timeout`][VoteHandler::timeout].
+  async fn voted(&self, vote: Vote);
+
+  /// How long the endpoint waits for [`voted`][VoteHandler::voted] before responding with a 503 (Service Unavailable) HTTP status code. (See [`FallibleVoteHandler::timeout`])
+  ///
+  /// Defaults to `None`, meaning the endpoint waits for as long as it takes.
+  #[inline(always)]
+  fn timeout(&self) -> Option<Duration> {
+    None
+  }
+}
+
+/// An async trait for adding an on-vote event handler that can fail to your application logic.
+///
+/// If [`try_voted`][FallibleVoteHandler::try_voted] errors, the endpoint responds with a 500 (Internal Server Error) HTTP status code, and if it takes longer than [`timeout`][FallibleVoteHandler::timeout], with a 503 (Service Unavailable) HTTP status code. Both make [Top.gg](https://top.gg) deliver the vote again later.
+///
+/// It's described as follows (without [`async_trait`]'s macro expansion):
+/// ```rust,no_run
+/// # use core::time::Duration;
+/// # use topgg::Vote;
+/// #[async_trait::async_trait]
+/// pub trait FallibleVoteHandler: Send + Sync + 'static {
+///   type Error: std::error::Error + Send + Sync + 'static;
+///
+///   async fn try_voted(&self, vote: Vote) -> Result<(), Self::Error>;
+///
+///   fn timeout(&self) -> Option<Duration> {
+///     None
+///   }
+/// }
+/// ```
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust,no_run
+/// use core::time::Duration;
+/// use std::io;
+/// use topgg::{FallibleVoteHandler, Vote};
+///
+/// struct MyVoteHandler {}
+///
+/// #[async_trait::async_trait]
+/// impl FallibleVoteHandler for MyVoteHandler {
+///   type Error = io::Error;
+///
+///   async fn try_voted(&self, vote: Vote) -> io::Result<()> {
+///     // store the vote in your database...
+///
+///     Ok(())
+///   }
+///
+///   fn timeout(&self) -> Option<Duration> {
+///     Some(Duration::from_secs(5))
+///   }
+/// }
+/// ```
+#[async_trait::async_trait]
+pub trait FallibleVoteHandler: Send + Sync + 'static {
+  /// The error that [`try_voted`][FallibleVoteHandler::try_voted] can return.
+  type Error: error::Error + Send + Sync + 'static;
+
+  /// Your vote handler's on-vote async callback. The endpoint returns a 200 (OK) HTTP status code if this method succeeds, otherwise a 500 (Internal Server Error) HTTP status code.
+  async fn try_voted(&self, vote: Vote) -> Result<(), Self::Error>;
+
+  /// How long the endpoint waits for [`try_voted`][FallibleVoteHandler::try_voted] before responding with a 503 (Service Unavailable) HTTP status code, which makes [Top.gg](https://top.gg) deliver the vote again later.
+  ///
+  /// The handler is not cancelled when this happens, it keeps running in the background instead.
+  ///
+  /// Defaults to `None`, meaning the endpoint waits for as long as it takes.
+  #[inline(always)]
+  fn timeout(&self) -> Option<Duration> {
+    None
+  }
+}
+
+#[async_trait::async_trait]
+impl<T> FallibleVoteHandler for T
+where
+  T: VoteHandler,
+{
+  type Error = Infallible;
+
+  #[inline(always)]
+  async fn try_voted(&self, vote: Vote) -> Result<(), Infallible> {
+    self.voted(vote).await;
+
+    Ok(())
+  }
+
+  #[inline(always)]
+  fn timeout(&self) -> Option<Duration> {
+    VoteHandler::timeout(self)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  /// Delivers a vote made from the specified vote page URL.
+  fn deliver(url: &str) -> Vote {
+    let query = url.split_once('?').map_or("", |(_, query)| query);
+
+    serde_json::from_value(serde_json::json!({
+      "bot": "1",
+      "user": "2",
+      "type": "upvote",
+      "query": format!("?{query}"),
+    }))
+    .unwrap()
+  }
+
+  #[test]
+  fn creates_vote_urls() {
+    assert_eq!(
+      vote_url_with_query(VoteTarget::Bot(1), &[("code", "a b")]).unwrap(),
+      "https://top.gg/bot/1/vote?code=a+b"
+    );
+    assert_eq!(
+      vote_url_with_query(VoteTarget::Server(2), &[("code", "a b")]).unwrap(),
+      "https://top.gg/servers/2/vote?code=a+b"
+    );
+    assert_eq!(
+      vote_url_with_query(VoteTarget::Bot(1), &[] as &[(&str, &str)]).unwrap(),
+      "https://top.gg/bot/1/vote"
+    );
+  }
+
+  #[test]
+  fn round_trips_query_strings() {
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Referral {
+      code: String,
+      note: String,
     }
+
+    let referral = Referral {
+      code: String::from("summer sale"),
+      note: String::from("a=b&c=d+e%20f"),
+    };
+
+    let vote = deliver(&vote_url_with_query(VoteTarget::Bot(1), &referral).unwrap());
+
+    assert_eq!(vote.query_as::<Referral>().unwrap(), referral);
+    assert_eq!(vote.query["note"], referral.note);
+  }
+
+  #[test]
+  fn decodes_spaces() {
+    let vote = deliver("https://top.gg/bot/1/vote?a=x+y&b=x%20y");
+
+    assert_eq!(vote.query["a"], "x y");
+    assert_eq!(vote.query["b"], "x y");
+  }
+
+  #[test]
+  fn keeps_repeated_keys() {
+    let vote = deliver(
+      &vote_url_with_query(
+        VoteTarget::Bot(1),
+        &[("tag", "a"), ("other", "b"), ("tag", "c&d")],
+      )
+      .unwrap(),
+    );
+
+    assert_eq!(vote.query["tag"], "c&d");
+    assert_eq!(vote.query_multimap()["tag"], ["a", "c&d"]);
+    assert_eq!(vote.query_multimap()["other"], ["b"]);
+  }
+
+  fn parse(target: &str) -> Result<Vote, serde_json::Error> {
+    serde_json::from_str(&format!(r#"{{{target}"user":"2","type":"upvote"}}"#))
+  }
+
+  #[test]
+  fn parses_targets() {
+    assert_eq!(parse(r#""bot":"1","#).unwrap().target, VoteTarget::Bot(1));
+    assert_eq!(
+      parse(r#""guild":"3","#).unwrap().target,
+      VoteTarget::Server(3)
+    );
+    assert_eq!(
+      parse(r#""bot":"1","guild":"3","#).unwrap().target,
+      VoteTarget::Server(3)
+    );
+    assert!(parse("")
+      .unwrap_err()
+      .to_string()
+      .contains("missing field `bot` or `guild`"));
+  }
+
+  #[test]
+  fn serializes_in_wire_format() {
+    for target in [r#""bot":"1","#, r#""guild":"3","#] {
+      let vote = parse(target).unwrap();
+      let json = serde_json::to_value(&vote).unwrap();
+      let again: Vote = serde_json::from_value(json.clone()).unwrap();
+
+      assert_eq!(again.target, vote.target);
+      assert_eq!(json.get("guild").is_some(), vote.target.is_server());
+      assert_eq!(json.get("bot").is_some(), vote.target.is_bot());
+    }
+
+    let vote = deliver("https://top.gg/bot/1/vote?a=b");
+    let again: Vote = serde_json::from_value(serde_json::to_value(&vote).unwrap()).unwrap();
+
+    assert_eq!(again.query_string(), "a=b");
+  }
+
+  #[cfg(any(feature = "actix-web", feature = "axum", feature = "rocket"))]
+  #[test]
+  fn authenticates_with_borrowed_password() {
+    let password = String::from("my-password");
+    let incoming = |authorization: &str| IncomingVote {
+      authorization: Some(authorization.to_owned()),
+      body: br#"{"bot":"1","user":"2","type":"upvote"}"#.to_vec(),
+    };
+
+    assert!(incoming("my-password").authenticate(&password).is_some());
+    assert!(incoming("another-password")
+      .authenticate(&password)
+      .is_none());
   }
 }
@@ -1,8 +1,12 @@
-use crate::{Vote, VoteHandler};
+use super::verify::{parse_event, parse_vote};
+use crate::{FallibleVoteHandler, Passwords, WebhookEventHandler, WebhookSecret, SIGNATURE_HEADER};
 use std::sync::Arc;
+use warp::hyper::body::Bytes;
 use warp::{body, header, http::StatusCode, path, Filter, Rejection, Reply};
 
-/// Creates a new `warp` [`Filter`] for adding an on-vote event handler to your application logic.
+/// Creates a new `warp` [`Filter`] for adding an on-vote event handler to your application logic. (See [`Passwords`])
+///
+/// `state` can also be a [`FallibleVoteHandler`].
 ///
 /// # Examples
 ///
@@ -41,31 +45,101 @@ use warp::{body, header, http::StatusCode, path, Filter, Rejection, Reply};
 /// }
 /// ```
 #[cfg_attr(docsrs, doc(cfg(feature = "warp")))]
-pub fn webhook<T>(
+pub fn webhook<P, T>(
   endpoint: &'static str,
-  password: String,
+  passwords: P,
   state: Arc<T>,
 ) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone
 where
-  T: VoteHandler,
+  P: Into<Passwords>,
+  T: FallibleVoteHandler,
 {
-  let password = Arc::new(password);
+  let passwords = Arc::new(passwords.into());
 
   warp::post()
     .and(path(endpoint))
-    .and(header("Authorization"))
-    .and(body::json())
-    .then(move |auth: String, vote: Vote| {
+    .and(header::optional::<String>("Authorization"))
+    .and(body::bytes())
+    .then(move |authorization: Option<String>, body: Bytes| {
       let current_state = Arc::clone(&state);
-      let current_password = Arc::clone(&password);
+      let current_passwords = Arc::clone(&passwords);
 
       async move {
-        if auth == *current_password {
-          current_state.voted(vote).await;
+        let result = match parse_vote(&current_passwords, authorization.as_deref(), &body) {
+          Ok(vote) => crate::handle_vote(&current_state, vote).await,
+          Err(err) => Err(err),
+        };
+
+        match result {
+          Ok(()) => StatusCode::OK,
+          Err(err) => StatusCode::from_u16(err.status_code()).unwrap(),
+        }
+      }
+    })
+}
+
+/// Creates a new `warp` [`Filter`] for adding an on-event handler for signed [Top.gg](https://top.gg) webhook deliveries to your application logic.
+///
+/// Deliveries with an invalid signature or a timestamp outside of the replay window are rejected with a 401 (Unauthorized) HTTP status code.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust,no_run
+/// use std::sync::Arc;
+/// use topgg::{WebhookEvent, WebhookEventHandler};
+/// use warp::Filter;
+///
+/// struct MyEventHandler {}
+///
+/// #[async_trait::async_trait]
+/// impl WebhookEventHandler for MyEventHandler {
+///   async fn handle(&self, event: WebhookEvent) {
+///     if let WebhookEvent::VoteCreate(vote) = event {
+///       println!("{} voted!", vote.user.name);
+///     }
+///   }
+/// }
+///
+/// # fn run() {
+/// let state = Arc::new(MyEventHandler {});
+///
+/// // POST /webhook
+/// let webhook = topgg::warp::webhook_v1("webhook", "my-webhook-secret", state);
+///
+/// let routes = warp::get().map(|| "Hello, World!").or(webhook);
+/// # }
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "warp")))]
+pub fn webhook_v1<S, T>(
+  endpoint: &'static str,
+  secret: S,
+  state: Arc<T>,
+) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone
+where
+  S: Into<WebhookSecret>,
+  T: WebhookEventHandler,
+{
+  let secret = Arc::new(secret.into());
+
+  warp::post()
+    .and(path(endpoint))
+    .and(header::optional::<String>(SIGNATURE_HEADER))
+    .and(body::bytes())
+    .then(move |signature: Option<String>, body: Bytes| {
+      let current_state = Arc::clone(&state);
+      let current_secret = Arc::clone(&secret);
+
+      async move {
+        match parse_event(&current_secret, signature.as_deref(), &body) {
+          Ok(event) => {
+            current_state.handle(event).await;
+
+            StatusCode::OK
+          }
 
-          StatusCode::OK
-        } else {
-          StatusCode::UNAUTHORIZED
+          Err(err) => StatusCode::from_u16(err.status_code()).unwrap(),
         }
       }
     })